@@ -1,4 +1,6 @@
 use gloo_utils::errors::JsError;
+use http::Method;
+use std::time::Duration;
 use thiserror::Error as ThisError;
 
 /// All the errors returned by this crate.
@@ -16,6 +18,118 @@ pub enum Error {
         #[from]
         serde_json::Error,
     ),
+    /// The request could not be completed, e.g. because the server is unreachable or a CORS check
+    /// failed.
+    #[error("{method} {url} failed: {source}")]
+    Network {
+        /// The method of the request.
+        method: Method,
+        /// The URL of the request.
+        url: String,
+        /// The underlying error.
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// The request was aborted through its abort signal.
+    #[error("{method} {url} was aborted")]
+    Aborted {
+        /// The method of the request.
+        method: Method,
+        /// The URL of the request.
+        url: String,
+    },
+    /// The request did not complete before its timeout elapsed.
+    ///
+    /// See [`RequestBuilder::timeout`](crate::http::RequestBuilder::timeout).
+    #[error("request timed out after {0:?}")]
+    Timeout(Duration),
+    /// The server responded with an unsuccessful status code.
+    ///
+    /// See [`Response::error_for_status`](crate::http::Response::error_for_status).
+    #[error("{url} responded with status {status} {status_text}")]
+    Status {
+        /// The URL of the response.
+        url: String,
+        /// The [HTTP status code](https://developer.mozilla.org/en-US/docs/Web/HTTP/Status).
+        status: u16,
+        /// The status message sent along with the status code.
+        status_text: String,
+    },
+    /// The body of a request or response was read after it had already been consumed.
+    #[error("the body of {url} has already been used")]
+    BodyUsed {
+        /// The URL of the request or response.
+        url: String,
+    },
+    /// The body of a request or response could not be decoded, e.g. because it is not valid JSON
+    /// or does not match the expected type.
+    #[error("failed to decode the body of {url}: {source}")]
+    Decode {
+        /// The URL of the request or response.
+        url: String,
+        /// The underlying error.
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// A resource changed while it was being downloaded in chunks.
+    ///
+    /// See [`Download`](crate::http::Download).
+    #[cfg(feature = "download")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "download")))]
+    #[error("{url} changed during the download")]
+    ResourceChanged {
+        /// The URL of the resource.
+        url: String,
+    },
+    /// A WebSocket connection closed before a message could be sent or answered.
+    #[cfg(feature = "websocket")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "websocket")))]
+    #[error("the WebSocket connection to {url} is closed")]
+    ConnectionClosed {
+        /// The URL of the WebSocket.
+        url: String,
+        /// Why the connection closed, if that is known.
+        #[source]
+        source: Option<crate::websocket::WebSocketError>,
+    },
+    /// A value could not be encoded as the body of a request.
+    ///
+    /// See [`RequestBuilder::encode`](crate::http::RequestBuilder::encode).
+    #[error("failed to encode the body: {0}")]
+    Encode(#[source] Box<dyn std::error::Error + Send + Sync>),
+    /// A value could not be encoded as query parameters.
+    ///
+    /// See [`RequestBuilder::serialize_query`](crate::http::RequestBuilder::serialize_query).
+    #[error("failed to encode the query: {0}")]
+    EncodeQuery(#[source] Box<dyn std::error::Error + Send + Sync>),
+    /// A URL could not be built.
+    ///
+    /// See [`Url`](crate::http::Url).
+    #[error("invalid URL: {0}")]
+    InvalidUrl(String),
+    /// A header could not be parsed or formatted as a
+    /// [`TypedHeader`](crate::http::TypedHeader).
+    #[error("invalid {name} header: {source}")]
+    InvalidHeader {
+        /// The name of the header.
+        name: String,
+        /// The underlying error.
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// The server responded to a JSON-RPC call with an error.
+    #[cfg(feature = "jsonrpc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "jsonrpc")))]
+    #[error("{0}")]
+    JsonRpc(#[from] crate::jsonrpc::RpcError),
+    /// The server returned errors for a GraphQL operation.
+    #[cfg(feature = "graphql")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphql")))]
+    #[error("{0}")]
+    GraphQl(#[from] crate::graphql::Errors),
+    /// The JavaScript global context does not provide a `fetch` function.
+    #[error("`fetch` is not available in this JavaScript global context")]
+    FetchUnavailable,
     /// Error returned by this crate
     #[error("{0}")]
     GlooError(String),
@@ -97,6 +97,8 @@ impl EventSource {
     /// to learn more.
     pub fn new(url: &str) -> Result<Self, JsError> {
         let es = web_sys::EventSource::new(url).map_err(js_to_js_error)?;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(%url, "EventSource connecting");
 
         Ok(Self { es })
     }
@@ -134,12 +136,18 @@ impl EventSource {
             .map_err(js_to_js_error)?;
 
         let error_callback: Closure<dyn FnMut(web_sys::Event)> = {
+            #[cfg(feature = "tracing")]
+            let es_url = self.es.url();
             Closure::wrap(Box::new(move |e: web_sys::Event| {
                 let is_connecting = e
                     .current_target()
                     .map(|target| target.unchecked_into::<web_sys::EventSource>())
                     .map(|es| es.ready_state() == web_sys::EventSource::CONNECTING)
                     .unwrap_or(false);
+                #[cfg(feature = "tracing")]
+                if is_connecting {
+                    tracing::debug!(url = %es_url, "EventSource reconnecting");
+                }
                 if !is_connecting {
                     let _ = message_sender.unbounded_send(StreamMessage::ErrorEvent);
                 };
@@ -168,6 +176,10 @@ impl EventSource {
     }
 
     fn close_and_notify(&mut self) {
+        #[cfg(feature = "tracing")]
+        if self.es.ready_state() != web_sys::EventSource::CLOSED {
+            tracing::debug!(url = %self.es.url(), "EventSource closed");
+        }
         self.es.close();
         // Fire an error event to cause all subscriber
         // streams to close down.
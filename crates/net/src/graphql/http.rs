@@ -0,0 +1,107 @@
+use super::{decode, GraphQlError, Operation};
+#[cfg(all(feature = "native", not(target_arch = "wasm32")))]
+use crate::http::native::Request;
+#[cfg(not(all(feature = "native", not(target_arch = "wasm32"))))]
+use crate::http::Request;
+use crate::Error;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::time::Duration;
+
+/// A GraphQL client which sends every operation as a `POST` request to one URL.
+///
+/// # Example
+///
+/// ```
+/// # use gloo_net::graphql::HttpClient;
+/// # async fn no_run() -> Result<(), gloo_net::Error> {
+/// let client = HttpClient::new("/graphql").header("Authorization", "Bearer token");
+///
+/// let data: serde_json::Value = client
+///     .query(
+///         "mutation Rename($name: String!) { rename(name: $name) { id } }",
+///         &serde_json::json!({ "name": "gloo" }),
+///     )
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct HttpClient {
+    url: String,
+    headers: Vec<(String, String)>,
+    timeout: Option<Duration>,
+}
+
+impl HttpClient {
+    /// Creates a client which sends operations to `url`.
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            headers: Vec::new(),
+            timeout: None,
+        }
+    }
+
+    /// Sets a header on every request.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Fails requests with [`Error::Timeout`] if no response has been received after `timeout`.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Runs a query or mutation and converts its data to `R`.
+    ///
+    /// Fails with [`Error::GraphQl`] if the server returned errors, even if it resolved part of
+    /// the operation.
+    pub async fn execute<R: DeserializeOwned>(&self, operation: &Operation) -> Result<R, Error> {
+        let mut builder = Request::post(self.url.as_str()).header(
+            "Accept",
+            "application/graphql-response+json, application/json",
+        );
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        let response = builder.json(&operation.to_json())?.send().await?;
+
+        // Servers may report invalid operations with an unsuccessful status and a list of errors.
+        let status_error = (!response.ok()).then(|| Error::Status {
+            url: response.url(),
+            status: response.status(),
+            status_text: response.status_text(),
+        });
+        let text = response.text().await?;
+        match (serde_json::from_str::<Value>(&text), status_error) {
+            (Ok(result), None) => decode(result, &self.url),
+            (Ok(result), Some(_)) if GraphQlError::from_list(result.get("errors")).is_some() => {
+                decode(result, &self.url)
+            }
+            (_, Some(error)) => Err(error),
+            (Err(error), None) => Err(Error::Decode {
+                url: response.url(),
+                source: Box::new(error),
+            }),
+        }
+    }
+
+    /// Runs the query or mutation `query` with `variables` and converts its data to `R`.
+    ///
+    /// `variables` has to serialize to an object, or to `null` for no variables.
+    pub async fn query<V, R>(&self, query: &str, variables: &V) -> Result<R, Error>
+    where
+        V: Serialize + ?Sized,
+        R: DeserializeOwned,
+    {
+        self.execute(&Operation::new(query).variables(variables)?)
+            .await
+    }
+}
@@ -0,0 +1,226 @@
+//! A [GraphQL](https://graphql.org/learn/) client.
+//!
+//! This API is provided over the following transports:
+//! - [HTTP][HttpClient], with the `http` feature: queries and mutations are sent as JSON `POST`
+//!   requests.
+//! - [WebSocket][WebSocketClient], with the `websocket` feature: subscriptions are run with the
+//!   [`graphql-transport-ws`](https://github.com/enisdenjo/graphql-ws/blob/master/PROTOCOL.md)
+//!   protocol.
+//!
+//! Variables and results are converted with `serde`. Errors returned by the server are reported
+//! as [`Error::GraphQl`](crate::Error::GraphQl).
+//!
+//! # Example
+//!
+//! ```
+//! # use gloo_net::graphql::{HttpClient, Operation};
+//! # use serde::Deserialize;
+//! #[derive(Deserialize)]
+//! struct Data {
+//!     user: User,
+//! }
+//!
+//! #[derive(Deserialize)]
+//! struct User {
+//!     name: String,
+//! }
+//!
+//! # async fn no_run() -> Result<(), gloo_net::Error> {
+//! let client = HttpClient::new("/graphql");
+//! let operation = Operation::new("query User($id: ID!) { user(id: $id) { name } }")
+//!     .variables(&serde_json::json!({ "id": "7" }))?;
+//! let data: Data = client.execute(&operation).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+// The shared parts are unused without a transport.
+#![cfg_attr(not(any(feature = "http", feature = "websocket")), allow(dead_code))]
+
+#[cfg(feature = "http")]
+mod http;
+#[cfg(feature = "websocket")]
+mod websocket;
+
+#[cfg(feature = "http")]
+pub use self::http::HttpClient;
+#[cfg(feature = "websocket")]
+pub use self::websocket::{Subscription, WebSocketClient};
+
+use crate::Error;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::fmt;
+
+/// A query, mutation or subscription, with its variables.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Operation {
+    query: String,
+    variables: Value,
+    operation_name: Option<String>,
+}
+
+impl Operation {
+    /// Creates an operation from a GraphQL document, without variables.
+    pub fn new(query: &str) -> Self {
+        Self {
+            query: query.to_string(),
+            variables: Value::Null,
+            operation_name: None,
+        }
+    }
+
+    /// Sets the variables, which have to serialize to an object.
+    pub fn variables<V: Serialize + ?Sized>(mut self, variables: &V) -> Result<Self, Error> {
+        self.variables = serde_json::to_value(variables).map_err(|e| Error::Encode(Box::new(e)))?;
+        Ok(self)
+    }
+
+    /// Selects the operation to run, if the document has more than one.
+    pub fn operation_name(mut self, operation_name: &str) -> Self {
+        self.operation_name = Some(operation_name.to_string());
+        self
+    }
+
+    /// The operation as a GraphQL request.
+    fn to_json(&self) -> Value {
+        let mut request = json!({ "query": self.query });
+        if !self.variables.is_null() {
+            request["variables"] = self.variables.clone();
+        }
+        if let Some(operation_name) = &self.operation_name {
+            request["operationName"] = json!(operation_name);
+        }
+        request
+    }
+}
+
+/// The errors returned by the server for an operation.
+///
+/// GraphQL servers may resolve part of an operation despite errors, which is then available as
+/// [`data`](Self::data).
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub struct Errors {
+    /// The errors, at least one.
+    pub errors: Vec<GraphQlError>,
+    /// The data which could be resolved, if any.
+    pub data: Option<Value>,
+}
+
+impl fmt::Display for Errors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("GraphQL error: ")?;
+        for (i, error) in self.errors.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An error returned by the server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphQlError {
+    /// A description of the error.
+    pub message: String,
+    /// Where the error is in the document, if it can be located.
+    pub locations: Vec<Location>,
+    /// The path of the field which failed, if the error happened while resolving one.
+    pub path: Vec<PathSegment>,
+    /// Additional information from the server, e.g. an error code.
+    pub extensions: Option<Value>,
+}
+
+impl GraphQlError {
+    fn from_value(error: &Value) -> Self {
+        let locations = error["locations"].as_array().map(|locations| {
+            locations
+                .iter()
+                .filter_map(|location| {
+                    Some(Location {
+                        line: location["line"].as_u64()?,
+                        column: location["column"].as_u64()?,
+                    })
+                })
+                .collect()
+        });
+        let path = error["path"].as_array().map(|path| {
+            path.iter()
+                .filter_map(|segment| match segment {
+                    Value::String(field) => Some(PathSegment::Field(field.clone())),
+                    index => index.as_u64().map(PathSegment::Index),
+                })
+                .collect()
+        });
+        Self {
+            message: error["message"].as_str().unwrap_or_default().to_string(),
+            locations: locations.unwrap_or_default(),
+            path: path.unwrap_or_default(),
+            extensions: error.get("extensions").cloned(),
+        }
+    }
+
+    /// Parses a list of errors, returning `None` if it is missing or empty.
+    fn from_list(errors: Option<&Value>) -> Option<Vec<Self>> {
+        let errors: Vec<_> = errors?.as_array()?.iter().map(Self::from_value).collect();
+        (!errors.is_empty()).then_some(errors)
+    }
+}
+
+impl fmt::Display for GraphQlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)?;
+        if !self.path.is_empty() {
+            let path: Vec<_> = self.path.iter().map(ToString::to_string).collect();
+            write!(f, " at {}", path.join("."))?;
+        }
+        Ok(())
+    }
+}
+
+/// A position in a GraphQL document, starting at 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    /// The line.
+    pub line: u64,
+    /// The column.
+    pub column: u64,
+}
+
+/// A segment of the path to a field in the result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    /// The name, or alias, of a field.
+    Field(String),
+    /// An index into a list.
+    Index(u64),
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathSegment::Field(field) => f.write_str(field),
+            PathSegment::Index(index) => write!(f, "{index}"),
+        }
+    }
+}
+
+/// Converts the `data` of an execution result to `R`, unless it has errors. `url` is where the
+/// result came from.
+fn decode<R: DeserializeOwned>(result: Value, url: &str) -> Result<R, Error> {
+    let data = result.get("data").filter(|data| !data.is_null()).cloned();
+    if let Some(errors) = GraphQlError::from_list(result.get("errors")) {
+        return Err(Errors { errors, data }.into());
+    }
+    let data = data.ok_or_else(|| Error::Decode {
+        url: url.to_string(),
+        source: "the result has neither data nor errors".into(),
+    })?;
+    R::deserialize(data).map_err(|e| Error::Decode {
+        url: url.to_string(),
+        source: Box::new(e),
+    })
+}
@@ -0,0 +1,484 @@
+use super::{decode, Errors, GraphQlError, Operation};
+use crate::websocket::futures::WebSocket;
+use crate::websocket::{Message, State, WebSocketError};
+use crate::Error;
+use futures_channel::{mpsc, oneshot};
+use futures_core::Stream;
+use futures_sink::Sink;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+use std::future::poll_fn;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::rc::{Rc, Weak};
+use std::task::{Context, Poll};
+
+/// The WebSocket sub-protocol spoken by the client.
+const PROTOCOL: &str = "graphql-transport-ws";
+
+/// A GraphQL client which runs subscriptions over a WebSocket, with the
+/// [`graphql-transport-ws`](https://github.com/enisdenjo/graphql-ws/blob/master/PROTOCOL.md)
+/// protocol.
+///
+/// Every subscription is a [`Stream`] of results. Subscriptions wait until the server has
+/// acknowledged the connection, and the client answers the pings of the server.
+///
+/// Once the connection closes, the subscriptions end, after an [`Error::ConnectionClosed`] unless
+/// the connection closed normally. Dropping the client closes the connection.
+///
+/// # Example
+///
+/// ```
+/// # use gloo_net::graphql::{Operation, WebSocketClient};
+/// # use futures::StreamExt;
+/// # async fn no_run() -> Result<(), gloo_net::Error> {
+/// let client = WebSocketClient::connect_with_payload(
+///     "wss://example.com/graphql",
+///     &serde_json::json!({ "token": "secret" }),
+/// )?;
+///
+/// let operation = Operation::new("subscription { price(symbol: \"GLOO\") }");
+/// let mut prices = client.subscribe::<serde_json::Value>(&operation).await?;
+/// while let Some(price) = prices.next().await {
+///     let price = price?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct WebSocketClient {
+    inner: Rc<Inner>,
+}
+
+struct Inner {
+    ws: RefCell<WebSocket>,
+    url: String,
+    next_id: Cell<u64>,
+    acknowledged: Cell<bool>,
+    closed: Cell<bool>,
+    acknowledgements: RefCell<Vec<oneshot::Sender<()>>>,
+    subscriptions: RefCell<HashMap<String, mpsc::UnboundedSender<Result<Value, Error>>>>,
+}
+
+impl WebSocketClient {
+    /// Connects to `url`.
+    pub fn connect(url: &str) -> Result<Self, Error> {
+        Self::open(url, None)
+    }
+
+    /// Connects to `url`, sending `payload` with the connection request, e.g. to authenticate.
+    pub fn connect_with_payload<P: Serialize + ?Sized>(
+        url: &str,
+        payload: &P,
+    ) -> Result<Self, Error> {
+        let payload = serde_json::to_value(payload).map_err(|e| Error::Encode(Box::new(e)))?;
+        Self::open(url, Some(payload))
+    }
+
+    fn open(url: &str, payload: Option<Value>) -> Result<Self, Error> {
+        let ws = WebSocket::open_with_protocol(url, PROTOCOL).map_err(Error::JsError)?;
+        let inner = Rc::new(Inner::new(ws));
+        let mut init = json!({ "type": "connection_init" });
+        if let Some(payload) = payload {
+            init["payload"] = payload;
+        }
+        wasm_bindgen_futures::spawn_local(run(Rc::downgrade(&inner), init));
+        Ok(Self { inner })
+    }
+
+    /// Starts a subscription, whose results are converted to `R`.
+    ///
+    /// Results with errors are returned as [`Error::GraphQl`]. Dropping the subscription stops
+    /// it on the server.
+    pub async fn subscribe<R: DeserializeOwned>(
+        &self,
+        operation: &Operation,
+    ) -> Result<Subscription<R>, Error> {
+        self.acknowledged().await?;
+
+        let id = self.inner.next_id.get().to_string();
+        self.inner.next_id.set(self.inner.next_id.get() + 1);
+        let (sender, receiver) = mpsc::unbounded();
+        self.inner
+            .subscriptions
+            .borrow_mut()
+            .insert(id.clone(), sender);
+        let subscription = Subscription {
+            inner: Rc::downgrade(&self.inner),
+            url: self.inner.url.clone(),
+            id,
+            receiver,
+            result: PhantomData,
+        };
+
+        let message = json!({
+            "id": subscription.id,
+            "type": "subscribe",
+            "payload": operation.to_json(),
+        });
+        self.inner.send(&message).await?;
+        Ok(subscription)
+    }
+
+    /// Waits until the server has acknowledged the connection.
+    async fn acknowledged(&self) -> Result<(), Error> {
+        if self.inner.acknowledged.get() {
+            return Ok(());
+        }
+        if self.inner.closed.get() {
+            return Err(closed(&self.inner.url));
+        }
+        let (sender, receiver) = oneshot::channel();
+        self.inner.acknowledgements.borrow_mut().push(sender);
+        receiver.await.map_err(|_| closed(&self.inner.url))
+    }
+}
+
+impl fmt::Debug for WebSocketClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebSocketClient")
+            .field("url", &self.inner.url)
+            .field("acknowledged", &self.inner.acknowledged.get())
+            .field("closed", &self.inner.closed.get())
+            .field("subscriptions", &self.inner.subscriptions.borrow().len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Inner {
+    fn new(ws: WebSocket) -> Self {
+        Self {
+            url: ws.url(),
+            ws: RefCell::new(ws),
+            next_id: Cell::new(1),
+            acknowledged: Cell::new(false),
+            closed: Cell::new(false),
+            acknowledgements: RefCell::new(Vec::new()),
+            subscriptions: RefCell::new(HashMap::new()),
+        }
+    }
+
+    async fn send(&self, message: &Value) -> Result<(), Error> {
+        if self.closed.get() {
+            return Err(closed(&self.url));
+        }
+        poll_fn(|cx| Pin::new(&mut *self.ws.borrow_mut()).poll_ready(cx))
+            .await
+            .map_err(|error| websocket_error(&self.url, error))?;
+        Pin::new(&mut *self.ws.borrow_mut())
+            .start_send(Message::Text(message.to_string()))
+            .map_err(|error| websocket_error(&self.url, error))
+    }
+
+    /// Sends `message` if the connection is open, ignoring failures.
+    fn send_now(&self, message: &Value) {
+        if let Ok(mut ws) = self.ws.try_borrow_mut() {
+            if !self.closed.get() && matches!(ws.state(), State::Open) {
+                let _ = Pin::new(&mut *ws).start_send(Message::Text(message.to_string()));
+            }
+        }
+    }
+
+    /// Handles a message of the server.
+    fn dispatch(&self, text: &str) {
+        let message: Value = match serde_json::from_str(text) {
+            Ok(message) => message,
+            Err(_) => return,
+        };
+        let id = message["id"].as_str().unwrap_or_default();
+        match message["type"].as_str().unwrap_or_default() {
+            "connection_ack" => {
+                self.acknowledged.set(true);
+                for acknowledgement in self.acknowledgements.borrow_mut().drain(..) {
+                    let _ = acknowledgement.send(());
+                }
+            }
+            "ping" => self.send_now(&json!({ "type": "pong" })),
+            "next" => {
+                if let Some(subscription) = self.subscriptions.borrow().get(id) {
+                    let _ = subscription.unbounded_send(Ok(message["payload"].clone()));
+                }
+            }
+            "error" => {
+                let subscription = self.subscriptions.borrow_mut().remove(id);
+                if let Some(subscription) = subscription {
+                    let errors = GraphQlError::from_list(message.get("payload"))
+                        .unwrap_or_else(|| vec![GraphQlError::from_value(&message["payload"])]);
+                    let _ = subscription.unbounded_send(Err(Errors { errors, data: None }.into()));
+                }
+            }
+            "complete" => {
+                self.subscriptions.borrow_mut().remove(id);
+            }
+            _ => {}
+        }
+    }
+
+    /// Ends the subscriptions, after `error` if there is one.
+    fn close(&self, error: Option<WebSocketError>) {
+        self.closed.set(true);
+        self.acknowledgements.borrow_mut().clear();
+        let subscriptions = std::mem::take(&mut *self.subscriptions.borrow_mut());
+        let error = match error {
+            Some(WebSocketError::ConnectionClose(event)) if event.code == 1000 => None,
+            error => error,
+        };
+        if let Some(error) = error {
+            for subscription in subscriptions.values() {
+                // The connection itself only fails with these errors, which are copied for every
+                // subscription.
+                let source = match &error {
+                    WebSocketError::ConnectionClose(event) => {
+                        WebSocketError::ConnectionClose(event.clone())
+                    }
+                    _ => WebSocketError::ConnectionError,
+                };
+                let _ = subscription.unbounded_send(Err(Error::ConnectionClosed {
+                    url: self.url.clone(),
+                    source: Some(source),
+                }));
+            }
+        }
+    }
+}
+
+/// Initializes the connection, then reads the messages of the WebSocket until it closes or the
+/// client is dropped.
+async fn run(inner: Weak<Inner>, init: Value) {
+    if let Some(inner) = inner.upgrade() {
+        if inner.send(&init).await.is_err() {
+            inner.close(Some(WebSocketError::ConnectionError));
+            return;
+        }
+    }
+    loop {
+        let message = poll_fn(|cx| match inner.upgrade() {
+            Some(inner) => Pin::new(&mut *inner.ws.borrow_mut()).poll_next(cx),
+            None => Poll::Ready(None),
+        })
+        .await;
+        let inner = match inner.upgrade() {
+            Some(inner) => inner,
+            None => return,
+        };
+        match message {
+            Some(Ok(Message::Text(text))) => inner.dispatch(&text),
+            Some(Ok(Message::Bytes(_))) => {}
+            Some(Err(error)) => {
+                inner.close(Some(error));
+                return;
+            }
+            None => {
+                inner.close(None);
+                return;
+            }
+        }
+    }
+}
+
+/// The results of a subscription, created by [`WebSocketClient::subscribe`].
+///
+/// The stream ends when the server completes the subscription, after an error from the server, or
+/// when the connection closes.
+pub struct Subscription<R> {
+    inner: Weak<Inner>,
+    url: String,
+    id: String,
+    receiver: mpsc::UnboundedReceiver<Result<Value, Error>>,
+    result: PhantomData<fn() -> R>,
+}
+
+impl<R: DeserializeOwned> Stream for Subscription<R> {
+    type Item = Result<R, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.receiver)
+            .poll_next(cx)
+            .map(|result| result.map(|result| result.and_then(|result| decode(result, &self.url))))
+    }
+}
+
+impl<R> Drop for Subscription<R> {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.upgrade() {
+            // Subscriptions which have ended are no longer known to the server.
+            let active = inner.subscriptions.borrow_mut().remove(&self.id).is_some();
+            if active {
+                inner.send_now(&json!({ "id": self.id, "type": "complete" }));
+            }
+        }
+    }
+}
+
+impl<R> fmt::Debug for Subscription<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Subscription")
+            .field("url", &self.url)
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+fn websocket_error(url: &str, error: WebSocketError) -> Error {
+    match error {
+        WebSocketError::MessageSendError(error) => Error::JsError(error),
+        error => Error::ConnectionClosed {
+            url: url.to_string(),
+            source: Some(error),
+        },
+    }
+}
+
+fn closed(url: &str) -> Error {
+    Error::ConnectionClosed {
+        url: url.to_string(),
+        source: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::websocket::events::CloseEvent;
+    use futures::{FutureExt, StreamExt};
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    /// A client connected to the echo server, whose messages are handed to `dispatch` by the test
+    /// rather than read from the connection.
+    fn client() -> WebSocketClient {
+        let ws_echo_server_url =
+            option_env!("WS_ECHO_SERVER_URL").expect("Did you set WS_ECHO_SERVER_URL?");
+        let ws = WebSocket::open(ws_echo_server_url).unwrap();
+        WebSocketClient {
+            inner: Rc::new(Inner::new(ws)),
+        }
+    }
+
+    fn operation() -> Operation {
+        Operation::new("subscription { count }")
+    }
+
+    #[wasm_bindgen_test]
+    async fn the_connection_is_initialized_and_read() {
+        let client = client();
+        // The echo server sends the init message back, so one of type `connection_ack`
+        // acknowledges the connection once `run` has sent it and read the reply.
+        let init = json!({ "type": "connection_ack" });
+        wasm_bindgen_futures::spawn_local(run(Rc::downgrade(&client.inner), init));
+        let mut subscription = client.subscribe::<Value>(&operation()).await.unwrap();
+        assert!(client.inner.acknowledged.get());
+
+        let next = json!({ "id": "1", "type": "next", "payload": { "data": { "count": 1 } } });
+        client.inner.send(&next).await.unwrap();
+        assert_eq!(
+            subscription.next().await.unwrap().unwrap(),
+            json!({ "count": 1 })
+        );
+    }
+
+    #[wasm_bindgen_test]
+    async fn subscriptions_wait_for_the_acknowledgement() {
+        let client = client();
+        let operation = operation();
+        let mut subscribe = Box::pin(client.subscribe::<Value>(&operation));
+        assert!((&mut subscribe).now_or_never().is_none());
+
+        client.inner.dispatch(r#"{"type":"connection_ack"}"#);
+        let subscription = subscribe.await.unwrap();
+        assert_eq!(subscription.id, "1");
+        assert!(client.inner.subscriptions.borrow().contains_key("1"));
+    }
+
+    #[wasm_bindgen_test]
+    async fn messages_are_routed_to_their_subscription() {
+        let client = client();
+        client.inner.dispatch(r#"{"type":"connection_ack"}"#);
+        let mut first = client.subscribe::<Value>(&operation()).await.unwrap();
+        let mut second = client.subscribe::<Value>(&operation()).await.unwrap();
+
+        client
+            .inner
+            .dispatch(r#"{"id":"2","type":"next","payload":{"data":{"count":2}}}"#);
+        client
+            .inner
+            .dispatch(r#"{"id":"1","type":"next","payload":{"data":{"count":1}}}"#);
+        assert_eq!(first.next().await.unwrap().unwrap(), json!({ "count": 1 }));
+        assert_eq!(second.next().await.unwrap().unwrap(), json!({ "count": 2 }));
+
+        client
+            .inner
+            .dispatch(r#"{"id":"1","type":"error","payload":[{"message":"denied"}]}"#);
+        match first.next().await.unwrap() {
+            Err(Error::GraphQl(errors)) => assert_eq!(errors.errors[0].message, "denied"),
+            result => panic!("expected a GraphQL error, got {result:?}"),
+        }
+        assert!(first.next().await.is_none());
+
+        client.inner.dispatch(r#"{"id":"2","type":"complete"}"#);
+        assert!(second.next().await.is_none());
+        assert!(client.inner.subscriptions.borrow().is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    async fn dropping_a_subscription_completes_it() {
+        let client = client();
+        client.inner.dispatch(r#"{"type":"connection_ack"}"#);
+        let subscription = client.subscribe::<Value>(&operation()).await.unwrap();
+        drop(subscription);
+        assert!(client.inner.subscriptions.borrow().is_empty());
+
+        // The echo server sends the subscription and its completion back.
+        loop {
+            let message = poll_fn(|cx| client.inner.ws.borrow_mut().poll_next_unpin(cx));
+            let message = match message.await {
+                Some(Ok(Message::Text(text))) => text,
+                message => panic!("expected a text message, got {message:?}"),
+            };
+            let message: Value = match serde_json::from_str(&message) {
+                Ok(message) => message,
+                Err(_) => continue,
+            };
+            if message["type"] == "complete" {
+                assert_eq!(message["id"], "1");
+                break;
+            }
+        }
+    }
+
+    #[wasm_bindgen_test]
+    async fn closing_ends_the_subscriptions() {
+        let client = client();
+        client.inner.dispatch(r#"{"type":"connection_ack"}"#);
+        let mut normal = client.subscribe::<Value>(&operation()).await.unwrap();
+        client
+            .inner
+            .close(Some(WebSocketError::ConnectionClose(CloseEvent {
+                code: 1000,
+                reason: String::new(),
+                was_clean: true,
+            })));
+        assert!(normal.next().await.is_none());
+        assert!(matches!(
+            client.subscribe::<Value>(&operation()).await,
+            Err(Error::ConnectionClosed { source: None, .. })
+        ));
+
+        let client = self::client();
+        client.inner.dispatch(r#"{"type":"connection_ack"}"#);
+        let mut failed = client.subscribe::<Value>(&operation()).await.unwrap();
+        client.inner.close(Some(WebSocketError::ConnectionError));
+        assert!(matches!(
+            failed.next().await,
+            Some(Err(Error::ConnectionClosed {
+                source: Some(WebSocketError::ConnectionError),
+                ..
+            }))
+        ));
+        assert!(failed.next().await.is_none());
+    }
+}
@@ -0,0 +1,257 @@
+use crate::http::{CacheControl, Headers, Interceptor, Next, Request, Response, SendFuture};
+use crate::{js_to_error, Error};
+use http::Method;
+use js_sys::{Date, Reflect};
+use std::fmt;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{Cache, CacheStorage};
+
+/// The header in which a [`ResponseCache`] records when a response was stored, in milliseconds
+/// since the Unix epoch.
+const STORED_AT: &str = "X-Gloo-Cache-Stored-At";
+
+/// Headers of a `304 Not Modified` response which replace those of the cached response.
+const REVALIDATED_HEADERS: [&str; 4] = ["Cache-Control", "ETag", "Expires", "Last-Modified"];
+
+/// How a [`ResponseCache`] chooses between the cache and the network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CachePolicy {
+    /// Serve fresh cached responses without contacting the server. Stale or missing responses
+    /// are fetched from the network.
+    CacheFirst,
+    /// Always ask the server first and only serve cached responses when the network fails.
+    NetworkFirst,
+    /// Serve cached responses immediately, even when stale, and revalidate stale ones in the
+    /// background so that the next request gets the updated response.
+    StaleWhileRevalidate,
+}
+
+/// An [`Interceptor`] which stores responses to `GET` requests in the browser's
+/// [Cache Storage](https://developer.mozilla.org/en-US/docs/Web/API/CacheStorage).
+///
+/// Cached responses are considered fresh for the `max-age` of their `Cache-Control` header.
+/// Responses marked `no-store` are never stored, `no-cache` ones are always revalidated. When a
+/// stale response has an `ETag`, it is revalidated with an `If-None-Match` request, and a `304 Not
+/// Modified` answer refreshes the cached response instead of downloading it again. If the network
+/// fails, the cached response is served even when stale, regardless of the policy.
+///
+/// Responses from the network are handed out as they are. Responses served from the cache have
+/// the URL of the request, but are never [`redirected`](Response::redirected) and have the
+/// `default` [type](Response::type_). Background revalidations of the
+/// [`StaleWhileRevalidate`](CachePolicy::StaleWhileRevalidate) policy are sent without running
+/// the interceptors which come after the cache. When Cache Storage is unavailable, e.g. outside
+/// of secure contexts, requests are simply passed on.
+///
+/// # Example
+///
+/// ```
+/// # use gloo_net::http::{CachePolicy, Client, ResponseCache};
+/// # async fn no_run() -> Result<(), gloo_net::Error> {
+/// let client = Client::builder()
+///     .base_url("https://api.example.com")
+///     .interceptor(ResponseCache::new("api", CachePolicy::StaleWhileRevalidate))
+///     .build();
+///
+/// let resp = client.get("/articles").send().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ResponseCache {
+    name: String,
+    policy: CachePolicy,
+}
+
+impl ResponseCache {
+    /// Creates a cache which stores responses in the Cache Storage cache called `name`.
+    pub fn new(name: &str, policy: CachePolicy) -> Self {
+        Self {
+            name: name.to_string(),
+            policy,
+        }
+    }
+
+    /// Deletes all responses stored by this cache.
+    pub async fn clear(&self) -> Result<(), Error> {
+        if let Some(caches) = cache_storage()? {
+            JsFuture::from(caches.delete(&self.name))
+                .await
+                .map_err(js_to_error)?;
+        }
+        Ok(())
+    }
+
+    async fn send(&self, request: Request, next: Next<'_>) -> Result<Response, Error> {
+        let caches = match cache_storage()? {
+            Some(caches) if request.method() == Method::GET => caches,
+            _ => return next.run(request).await,
+        };
+        let cache: Cache = JsFuture::from(caches.open(&self.name))
+            .await
+            .map_err(js_to_error)?
+            .unchecked_into();
+        let key = web_sys::Request::clone(&request.raw).map_err(js_to_error)?;
+        let cached = JsFuture::from(cache.match_with_request(&key))
+            .await
+            .map_err(js_to_error)?
+            .dyn_into::<web_sys::Response>()
+            .ok();
+
+        let fresh = cached.as_ref().map_or(false, is_fresh);
+        match (self.policy, cached) {
+            (CachePolicy::CacheFirst, Some(cached)) if fresh => restore(&cached, &key),
+            (CachePolicy::StaleWhileRevalidate, Some(cached)) => {
+                let stale = if fresh {
+                    None
+                } else {
+                    Some(cached.clone().map_err(js_to_error)?)
+                };
+                let response = restore(&cached, &key);
+                if let Some(stale) = stale {
+                    spawn_local(async move {
+                        if let Ok(response) = revalidate(request, Some(&stale)).dispatch().await {
+                            let _ = store(&cache, &key, Some(stale), response).await;
+                        }
+                    });
+                }
+                response
+            }
+            (_, cached) => {
+                let request = revalidate(request, cached.as_ref());
+                match (next.run(request).await, cached) {
+                    (Ok(response), cached) => store(&cache, &key, cached, response).await,
+                    (Err(Error::Network { .. } | Error::Timeout(_)), Some(cached)) => {
+                        restore(&cached, &key)
+                    }
+                    (Err(e), _) => Err(e),
+                }
+            }
+        }
+    }
+}
+
+impl Interceptor for ResponseCache {
+    fn intercept<'a>(&'a self, request: Request, next: Next<'a>) -> SendFuture<'a> {
+        Box::pin(self.send(request, next))
+    }
+}
+
+impl fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("name", &self.name)
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+/// The `caches` object of the global scope, if there is one.
+fn cache_storage() -> Result<Option<CacheStorage>, Error> {
+    let caches =
+        Reflect::get(&js_sys::global(), &JsValue::from_str("caches")).map_err(js_to_error)?;
+    Ok(caches.dyn_into::<CacheStorage>().ok())
+}
+
+/// Makes `request` conditional on the `ETag` of the `cached` response, if it has one.
+fn revalidate(request: Request, cached: Option<&web_sys::Response>) -> Request {
+    let etag = cached.and_then(|cached| Headers::from_raw(cached.headers()).get("ETag"));
+    if let Some(etag) = etag {
+        request.headers().set("If-None-Match", &etag);
+    }
+    request
+}
+
+/// Stores `response` in `cache` if it may be cached, returning the response to hand out.
+///
+/// A `304 Not Modified` response refreshes the `cached` response instead.
+async fn store(
+    cache: &Cache,
+    key: &web_sys::Request,
+    cached: Option<web_sys::Response>,
+    response: Response,
+) -> Result<Response, Error> {
+    let (stored, response) = match cached {
+        Some(cached) if response.status() == 304 => {
+            let refreshed = rebuild(&cached, &response.headers(), Some(Date::now()))?;
+            let stored = refreshed.clone().map_err(js_to_error)?;
+            (stored, restore(&refreshed, key)?)
+        }
+        _ if is_cacheable(&response) => {
+            let copy = response.try_clone()?.into();
+            (
+                rebuild(&copy, &Headers::new(), Some(Date::now()))?,
+                response,
+            )
+        }
+        _ => return Ok(response),
+    };
+    // Failing to store the response, e.g. because the storage quota is exceeded, must not fail
+    // the request.
+    let _ = JsFuture::from(cache.put_with_request(key, &stored)).await;
+    Ok(response)
+}
+
+/// Rebuilds `response` with the revalidated `updates` applied. The time the response is stored
+/// at is recorded if there is one, and removed otherwise.
+fn rebuild(
+    response: &web_sys::Response,
+    updates: &Headers,
+    stored_at: Option<f64>,
+) -> Result<web_sys::Response, Error> {
+    let headers = Headers::new();
+    for (name, value) in Headers::from_raw(response.headers()).entries() {
+        if !name.eq_ignore_ascii_case(STORED_AT) {
+            headers.append(&name, &value);
+        }
+    }
+    for name in REVALIDATED_HEADERS {
+        if let Some(value) = updates.get(name) {
+            headers.set(name, &value);
+        }
+    }
+    if let Some(stored_at) = stored_at {
+        headers.set(STORED_AT, &stored_at.to_string());
+    }
+
+    let mut init = web_sys::ResponseInit::new();
+    init.status(response.status());
+    init.status_text(&response.status_text());
+    init.headers(&headers.into_raw());
+    web_sys::Response::new_with_opt_readable_stream_and_init(response.body().as_ref(), &init)
+        .map_err(js_to_error)
+}
+
+/// The response to hand out for a `cached` one: without the time it was stored at, and with the
+/// URL of the request it is stored for, as rebuilt responses have none.
+fn restore(cached: &web_sys::Response, key: &web_sys::Request) -> Result<Response, Error> {
+    Response::from(rebuild(cached, &Headers::new(), None)?).with_url(&key.url())
+}
+
+/// Whether a successful `response` may be stored.
+fn is_cacheable(response: &Response) -> bool {
+    let headers = response.headers();
+    let no_store = match headers.typed_get::<CacheControl>() {
+        Ok(cache_control) => cache_control.map_or(false, |cache_control| cache_control.no_store),
+        // Directives which cannot be parsed might include `no-store`.
+        Err(_) => true,
+    };
+    response.status() == 200
+        && !no_store
+        && headers.get("Vary").map_or(true, |vary| vary.trim() != "*")
+}
+
+/// Whether the `max-age` of a cached response has not elapsed yet.
+fn is_fresh(cached: &web_sys::Response) -> bool {
+    let headers = Headers::from_raw(cached.headers());
+    let stored_at = match headers.get(STORED_AT).and_then(|at| at.parse::<f64>().ok()) {
+        Some(stored_at) => stored_at,
+        None => return false,
+    };
+    let max_age = match headers.typed_get::<CacheControl>() {
+        Ok(Some(cache_control)) if !cache_control.no_cache => cache_control.max_age,
+        _ => None,
+    };
+    max_age.map_or(false, |max_age| {
+        Date::now() < stored_at + max_age.as_millis() as f64
+    })
+}
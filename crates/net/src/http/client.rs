@@ -0,0 +1,295 @@
+use crate::http::{Headers, Request, RequestBuilder, SendFuture, Transport, Url};
+use http::Method;
+use std::fmt;
+use std::rc::Rc;
+use web_sys::{RequestCredentials, RequestMode};
+
+/// Runs around every request sent by a [`Client`].
+///
+/// An interceptor receives the request together with the rest of the chain, [`Next`]. It can
+/// modify the request before passing it on, inspect or replace the response, or send the request
+/// more than once.
+///
+/// # Example
+///
+/// Refreshing an access token when the server answers with `401 Unauthorized`:
+///
+/// ```
+/// use gloo_net::http::{Interceptor, Next, Request, SendFuture};
+/// # async fn refresh_token() -> Result<String, gloo_net::Error> { todo!() }
+///
+/// struct RefreshToken;
+///
+/// impl Interceptor for RefreshToken {
+///     fn intercept<'a>(&'a self, request: Request, next: Next<'a>) -> SendFuture<'a> {
+///         Box::pin(async move {
+///             let retry = request.try_clone()?;
+///             let response = next.run(request).await?;
+///             if response.status() != 401 {
+///                 return Ok(response);
+///             }
+///             let token = refresh_token().await?;
+///             retry
+///                 .headers()
+///                 .set("Authorization", &format!("Bearer {}", token));
+///             next.run(retry).await
+///         })
+///     }
+/// }
+/// ```
+pub trait Interceptor {
+    /// Handles `request`, usually by passing it on to `next`.
+    fn intercept<'a>(&'a self, request: Request, next: Next<'a>) -> SendFuture<'a>;
+}
+
+/// The remainder of an interceptor chain.
+///
+/// Running it passes the request to the next [`Interceptor`], or sends it once all interceptors
+/// have run. `Next` is `Copy`, so an interceptor may run it several times.
+#[derive(Clone, Copy)]
+pub struct Next<'a> {
+    interceptors: &'a [Rc<dyn Interceptor>],
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(interceptors: &'a [Rc<dyn Interceptor>]) -> Self {
+        Self { interceptors }
+    }
+
+    /// Passes `request` on to the rest of the chain.
+    pub fn run(self, request: Request) -> SendFuture<'a> {
+        match self.interceptors.split_first() {
+            Some((first, rest)) => first.intercept(request, Next::new(rest)),
+            None => Box::pin(request.dispatch()),
+        }
+    }
+}
+
+impl fmt::Debug for Next<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Next")
+            .field("remaining", &self.interceptors.len())
+            .finish()
+    }
+}
+
+/// A reusable configuration for sending requests to the same API.
+///
+/// Requests created through a client are resolved against its base URL and start out with its
+/// default headers, credentials and mode. They are sent through the client's [`Interceptor`]s,
+/// in the order they were added.
+///
+/// A client is cheap to clone, clones share the same configuration.
+///
+/// # Example
+///
+/// ```
+/// # use gloo_net::http::Client;
+/// # async fn no_run() -> Result<(), gloo_net::Error> {
+/// let client = Client::builder()
+///     .base_url("https://api.example.com/v1")
+///     .header("Accept", "application/json")
+///     .build();
+///
+/// // Sends `GET https://api.example.com/v1/users?page=2`
+/// let resp = client.get("/users").query([("page", "2")]).send().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Client {
+    inner: Rc<ClientConfig>,
+}
+
+struct ClientConfig {
+    base_url: Option<String>,
+    headers: Vec<(String, String)>,
+    credentials: Option<RequestCredentials>,
+    mode: Option<RequestMode>,
+    interceptors: Vec<Rc<dyn Interceptor>>,
+    transport: Option<Rc<dyn Transport>>,
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Client {
+    /// Creates a client without a base URL, default headers or interceptors.
+    pub fn new() -> Self {
+        Self::builder().build()
+    }
+
+    /// Returns an instance of client builder
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    /// The base URL paths are resolved against, if any.
+    pub fn base_url(&self) -> Option<&str> {
+        self.inner.base_url.as_deref()
+    }
+
+    /// Creates a request with the given method.
+    ///
+    /// `path` is appended to the base URL, unless it is already an absolute `http(s)` URL.
+    pub fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let config = &self.inner;
+        let url = match &config.base_url {
+            Some(base) => Url::new(base).join(path).to_string(),
+            None => path.to_string(),
+        };
+
+        let headers = Headers::new();
+        for (name, value) in &config.headers {
+            headers.append(name, value);
+        }
+        let mut builder = RequestBuilder::new(url)
+            .method(method)
+            .headers(headers)
+            .interceptors(config.interceptors.clone())
+            .shared_transport(config.transport.clone());
+        if let Some(credentials) = config.credentials {
+            builder = builder.credentials(credentials);
+        }
+        if let Some(mode) = config.mode {
+            builder = builder.mode(mode);
+        }
+        builder
+    }
+
+    /// Creates a new [`GET`][Method::GET] request.
+    pub fn get(&self, path: &str) -> RequestBuilder {
+        self.request(Method::GET, path)
+    }
+
+    /// Creates a new [`POST`][Method::POST] request.
+    pub fn post(&self, path: &str) -> RequestBuilder {
+        self.request(Method::POST, path)
+    }
+
+    /// Creates a new [`PUT`][Method::PUT] request.
+    pub fn put(&self, path: &str) -> RequestBuilder {
+        self.request(Method::PUT, path)
+    }
+
+    /// Creates a new [`DELETE`][Method::DELETE] request.
+    pub fn delete(&self, path: &str) -> RequestBuilder {
+        self.request(Method::DELETE, path)
+    }
+
+    /// Creates a new [`PATCH`][Method::PATCH] request.
+    pub fn patch(&self, path: &str) -> RequestBuilder {
+        self.request(Method::PATCH, path)
+    }
+}
+
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only the names of the headers, as their values may be credentials.
+        let headers: Vec<_> = self.inner.headers.iter().map(|(name, _)| name).collect();
+        f.debug_struct("Client")
+            .field("base_url", &self.inner.base_url)
+            .field("headers", &headers)
+            .field("credentials", &self.inner.credentials)
+            .field("mode", &self.inner.mode)
+            .field("interceptors", &self.inner.interceptors.len())
+            .finish()
+    }
+}
+
+/// A builder for [`Client`].
+pub struct ClientBuilder {
+    config: ClientConfig,
+}
+
+impl Default for ClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClientBuilder {
+    /// Creates a builder for a client without any defaults.
+    pub fn new() -> Self {
+        Self {
+            config: ClientConfig {
+                base_url: None,
+                headers: Vec::new(),
+                credentials: None,
+                mode: None,
+                interceptors: Vec::new(),
+                transport: None,
+            },
+        }
+    }
+
+    /// Sets the URL request paths are appended to.
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.config.base_url = Some(base_url.to_string());
+        self
+    }
+
+    /// Replace _all_ the default headers.
+    pub fn headers(mut self, headers: Headers) -> Self {
+        self.config.headers = headers.entries().collect();
+        self
+    }
+
+    /// Sets a default header.
+    pub fn header(mut self, key: &str, value: &str) -> Self {
+        self.config
+            .headers
+            .retain(|(name, _)| !name.eq_ignore_ascii_case(key));
+        self.config
+            .headers
+            .push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Sets the default credentials mode of requests.
+    pub fn credentials(mut self, credentials: RequestCredentials) -> Self {
+        self.config.credentials = Some(credentials);
+        self
+    }
+
+    /// Sets the default mode of requests.
+    pub fn mode(mut self, mode: RequestMode) -> Self {
+        self.config.mode = Some(mode);
+        self
+    }
+
+    /// Appends an interceptor to the chain.
+    ///
+    /// Interceptors run in the order they were added: the first one sees the request first and
+    /// the response last.
+    pub fn interceptor(mut self, interceptor: impl Interceptor + 'static) -> Self {
+        self.config.interceptors.push(Rc::new(interceptor));
+        self
+    }
+
+    /// Sends requests with `transport` instead of `fetch`.
+    ///
+    /// The transport runs after all interceptors.
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.config.transport = Some(Rc::new(transport));
+        self
+    }
+
+    /// Builds the client.
+    pub fn build(self) -> Client {
+        Client {
+            inner: Rc::new(self.config),
+        }
+    }
+}
+
+impl fmt::Debug for ClientBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientBuilder")
+            .field("base_url", &self.config.base_url)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,181 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::error::Error as StdError;
+
+/// The error returned by a [`BodyCodec`].
+pub type CodecError = Box<dyn StdError + Send + Sync>;
+
+/// A format for (de)serializing request and response bodies.
+///
+/// Used with [`RequestBuilder::encode`](super::RequestBuilder::encode),
+/// [`RequestBuilder::accept`](super::RequestBuilder::accept) and
+/// [`Response::decode`](super::Response::decode). Implementations are provided for [`Json`],
+/// [`Cbor`], [`MessagePack`] and [`UrlEncoded`], each behind the feature of the same name.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "cbor")]
+/// # async fn no_run() -> Result<(), gloo_net::Error> {
+/// use gloo_net::http::{Cbor, Request};
+///
+/// let resp = Request::post("/items")
+///     .accept(Cbor)
+///     .encode(Cbor, &["first", "second"])?
+///     .send()
+///     .await?;
+/// let ids: Vec<u32> = resp.decode(Cbor).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait BodyCodec {
+    /// The MIME type of the format, sent in the `Content-Type` and `Accept` headers.
+    fn content_type(&self) -> &'static str;
+
+    /// Whether a body with the given MIME type, without parameters, is in this format.
+    fn matches(&self, mime_type: &str) -> bool {
+        mime_type.eq_ignore_ascii_case(self.content_type())
+    }
+
+    /// Serializes `value` into a body.
+    fn encode<T: Serialize + ?Sized>(&self, value: &T) -> Result<Vec<u8>, CodecError>;
+
+    /// Deserializes a body.
+    fn decode<T: DeserializeOwned>(&self, body: &[u8]) -> Result<T, CodecError>;
+}
+
+/// The `application/json` format.
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json;
+
+#[cfg(feature = "json")]
+impl BodyCodec for Json {
+    fn content_type(&self) -> &'static str {
+        "application/json"
+    }
+
+    /// Also matches structured syntax suffixes, such as `application/problem+json`.
+    fn matches(&self, mime_type: &str) -> bool {
+        let mime_type = mime_type.to_ascii_lowercase();
+        mime_type == "application/json" || mime_type.ends_with("+json")
+    }
+
+    fn encode<T: Serialize + ?Sized>(&self, value: &T) -> Result<Vec<u8>, CodecError> {
+        Ok(serde_json::to_vec(value)?)
+    }
+
+    fn decode<T: DeserializeOwned>(&self, body: &[u8]) -> Result<T, CodecError> {
+        Ok(serde_json::from_slice(body)?)
+    }
+}
+
+/// The `application/cbor` format.
+#[cfg(feature = "cbor")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cbor")))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Cbor;
+
+#[cfg(feature = "cbor")]
+impl BodyCodec for Cbor {
+    fn content_type(&self) -> &'static str {
+        "application/cbor"
+    }
+
+    fn encode<T: Serialize + ?Sized>(&self, value: &T) -> Result<Vec<u8>, CodecError> {
+        let mut body = Vec::new();
+        ciborium::ser::into_writer(value, &mut body)?;
+        Ok(body)
+    }
+
+    fn decode<T: DeserializeOwned>(&self, body: &[u8]) -> Result<T, CodecError> {
+        Ok(ciborium::de::from_reader(body)?)
+    }
+}
+
+/// The `application/msgpack` format.
+///
+/// Structs are encoded as maps rather than arrays, so that they can be read by clients that do
+/// not know the field order.
+#[cfg(feature = "msgpack")]
+#[cfg_attr(docsrs, doc(cfg(feature = "msgpack")))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MessagePack;
+
+#[cfg(feature = "msgpack")]
+impl BodyCodec for MessagePack {
+    fn content_type(&self) -> &'static str {
+        "application/msgpack"
+    }
+
+    /// Also matches the unofficial `application/x-msgpack` and `application/vnd.msgpack`.
+    fn matches(&self, mime_type: &str) -> bool {
+        [
+            "application/msgpack",
+            "application/x-msgpack",
+            "application/vnd.msgpack",
+        ]
+        .iter()
+        .any(|known| mime_type.eq_ignore_ascii_case(known))
+    }
+
+    fn encode<T: Serialize + ?Sized>(&self, value: &T) -> Result<Vec<u8>, CodecError> {
+        Ok(rmp_serde::to_vec_named(value)?)
+    }
+
+    fn decode<T: DeserializeOwned>(&self, body: &[u8]) -> Result<T, CodecError> {
+        Ok(rmp_serde::from_slice(body)?)
+    }
+}
+
+/// The `application/x-www-form-urlencoded` format, as submitted by HTML forms.
+///
+/// Only flat structures of strings, numbers and booleans can be encoded.
+#[cfg(feature = "urlencoded")]
+#[cfg_attr(docsrs, doc(cfg(feature = "urlencoded")))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UrlEncoded;
+
+#[cfg(feature = "urlencoded")]
+impl BodyCodec for UrlEncoded {
+    fn content_type(&self) -> &'static str {
+        "application/x-www-form-urlencoded"
+    }
+
+    fn encode<T: Serialize + ?Sized>(&self, value: &T) -> Result<Vec<u8>, CodecError> {
+        Ok(serde_urlencoded::to_string(value)?.into_bytes())
+    }
+
+    fn decode<T: DeserializeOwned>(&self, body: &[u8]) -> Result<T, CodecError> {
+        Ok(serde_urlencoded::from_bytes(body)?)
+    }
+}
+
+/// Decodes `body` with the first enabled codec that matches `content_type`.
+///
+/// Returns `None` if none of them does.
+pub(crate) fn decode_negotiated<T: DeserializeOwned>(
+    content_type: &str,
+    body: &[u8],
+) -> Option<Result<T, CodecError>> {
+    let mime_type = content_type.split(';').next().unwrap_or_default().trim();
+    #[cfg(feature = "json")]
+    if Json.matches(mime_type) {
+        return Some(Json.decode(body));
+    }
+    #[cfg(feature = "cbor")]
+    if Cbor.matches(mime_type) {
+        return Some(Cbor.decode(body));
+    }
+    #[cfg(feature = "msgpack")]
+    if MessagePack.matches(mime_type) {
+        return Some(MessagePack.decode(body));
+    }
+    #[cfg(feature = "urlencoded")]
+    if UrlEncoded.matches(mime_type) {
+        return Some(UrlEncoded.decode(body));
+    }
+    let _ = (mime_type, body);
+    None
+}
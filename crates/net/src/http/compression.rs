@@ -0,0 +1,149 @@
+use flate2::read::{MultiGzDecoder, ZlibDecoder};
+use flate2::write::{GzEncoder, ZlibEncoder};
+use flate2::Compression;
+use std::io::{self, Read, Write};
+
+/// A `Content-Encoding` which bodies can be compressed with.
+///
+/// Used with [`RequestBuilder::compress`](super::RequestBuilder::compress) and
+/// [`Response::decompress`](super::Response::decompress).
+#[cfg_attr(docsrs, doc(cfg(feature = "compression")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentEncoding {
+    /// The gzip format.
+    Gzip,
+    /// The zlib format, which HTTP calls `deflate`.
+    Deflate,
+}
+
+impl ContentEncoding {
+    /// The name of the encoding in the `Content-Encoding` header.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Deflate => "deflate",
+        }
+    }
+
+    /// Compresses `data` in Rust.
+    pub(crate) fn encode(self, data: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            Self::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+            Self::Deflate => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+        }
+    }
+
+    /// Decompresses `data` in Rust.
+    pub(crate) fn decode(self, data: &[u8]) -> io::Result<Vec<u8>> {
+        let mut decoded = Vec::new();
+        match self {
+            Self::Gzip => MultiGzDecoder::new(data).read_to_end(&mut decoded)?,
+            Self::Deflate => ZlibDecoder::new(data).read_to_end(&mut decoded)?,
+        };
+        Ok(decoded)
+    }
+}
+
+pub(crate) use web::{compress, decompress, Decompressed};
+
+/// Compression with the `CompressionStream` and `DecompressionStream` APIs, falling back to
+/// [`ContentEncoding::encode`] and [`ContentEncoding::decode`] where they are missing.
+mod web {
+    use super::ContentEncoding;
+    use crate::{js_to_error, Error};
+    use js_sys::{Array, ArrayBuffer, Function, Reflect, Uint8Array};
+    use wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+    use web_sys::ReadableStream;
+
+    /// Compresses `body`, returning the compressed bytes as a `Uint8Array`.
+    pub(crate) async fn compress(
+        body: ReadableStream,
+        encoding: ContentEncoding,
+    ) -> Result<Uint8Array, Error> {
+        match pipe_through(&body, "CompressionStream", encoding)? {
+            Some(compressed) => read(&compressed).await,
+            None => {
+                let body = read(&body).await?.to_vec();
+                let compressed = encoding
+                    .encode(&body)
+                    .map_err(|e| Error::Encode(Box::new(e)))?;
+                Ok(Uint8Array::from(compressed.as_slice()))
+            }
+        }
+    }
+
+    /// A decompressed body.
+    pub(crate) enum Decompressed {
+        /// Decompressed by `DecompressionStream` as it is read, so corrupt data only surfaces
+        /// then.
+        Stream(ReadableStream),
+        /// Read and decompressed up front.
+        Bytes(Vec<u8>),
+    }
+
+    /// Decompresses `body`, which was received from `url`.
+    pub(crate) async fn decompress(
+        body: ReadableStream,
+        encoding: ContentEncoding,
+        url: &str,
+    ) -> Result<Decompressed, Error> {
+        match pipe_through(&body, "DecompressionStream", encoding)? {
+            Some(decompressed) => Ok(Decompressed::Stream(decompressed)),
+            None => {
+                let body = read(&body).await?.to_vec();
+                let decompressed = encoding.decode(&body).map_err(|e| Error::Decode {
+                    url: url.to_string(),
+                    source: Box::new(e),
+                })?;
+                Ok(Decompressed::Bytes(decompressed))
+            }
+        }
+    }
+
+    /// Pipes `body` through a new instance of the transform stream `class`, if the global scope
+    /// provides it.
+    fn pipe_through(
+        body: &ReadableStream,
+        class: &str,
+        encoding: ContentEncoding,
+    ) -> Result<Option<ReadableStream>, Error> {
+        let constructor = Reflect::get(&js_sys::global(), &JsValue::from_str(class))
+            .map_err(js_to_error)?
+            .dyn_into::<Function>();
+        let constructor = match constructor {
+            Ok(constructor) => constructor,
+            Err(_) => return Ok(None),
+        };
+        let transform = Reflect::construct(
+            &constructor,
+            &Array::of1(&JsValue::from_str(encoding.as_str())),
+        )
+        .map_err(js_to_error)?;
+        let pipe: Function = Reflect::get(body, &JsValue::from_str("pipeThrough"))
+            .map_err(js_to_error)?
+            .unchecked_into();
+        let piped = pipe.call1(body, &transform).map_err(js_to_error)?;
+        Ok(Some(piped.unchecked_into()))
+    }
+
+    /// Reads `stream` to completion.
+    async fn read(stream: &ReadableStream) -> Result<Uint8Array, Error> {
+        let response =
+            web_sys::Response::new_with_opt_readable_stream(Some(stream)).map_err(js_to_error)?;
+        let promise = response.array_buffer().map_err(js_to_error)?;
+        let buffer: ArrayBuffer = JsFuture::from(promise)
+            .await
+            .map_err(js_to_error)?
+            .unchecked_into();
+        Ok(Uint8Array::new(&buffer))
+    }
+}
@@ -0,0 +1,189 @@
+use crate::http::{Interceptor, Next, Request, Response, SendFuture};
+use crate::Error;
+use futures_channel::oneshot;
+use gloo_utils::errors::JsError;
+use http::Method;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+
+type Waiters = Vec<oneshot::Sender<Result<Response, Error>>>;
+
+/// An [`Interceptor`] which coalesces identical in-flight `GET` requests into a single request.
+///
+/// Requests are identical when they have the same URL and headers. While a request is in flight,
+/// identical requests do not hit the network but wait for it to finish, and each of them receives
+/// its own copy of the response. Only the request which was sent first passes through the
+/// interceptors after this one.
+///
+/// If the first request is dropped before it completes, the requests waiting for it are sent on
+/// their own.
+///
+/// # Example
+///
+/// ```
+/// # use gloo_net::http::{Client, Deduplicate};
+/// # async fn no_run() -> Result<(), gloo_net::Error> {
+/// let client = Client::builder().interceptor(Deduplicate::new()).build();
+///
+/// // Only one request is sent to the server.
+/// let (first, second) = futures::join!(
+///     client.get("/profile").send(),
+///     client.get("/profile").send(),
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct Deduplicate {
+    in_flight: RefCell<HashMap<String, Waiters>>,
+}
+
+impl Deduplicate {
+    /// Creates an interceptor without any requests in flight.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    async fn send(&self, request: Request, next: Next<'_>) -> Result<Response, Error> {
+        if request.method() != Method::GET {
+            return next.run(request).await;
+        }
+
+        let key = request_key(&request);
+        let waiting = {
+            let mut in_flight = self.in_flight.borrow_mut();
+            match in_flight.get_mut(&key) {
+                Some(waiters) => {
+                    let (sender, receiver) = oneshot::channel();
+                    waiters.push(sender);
+                    Some(receiver)
+                }
+                None => {
+                    in_flight.insert(key.clone(), Vec::new());
+                    None
+                }
+            }
+        };
+        if let Some(receiver) = waiting {
+            return match receiver.await {
+                Ok(result) => result,
+                // The first request was dropped.
+                Err(oneshot::Canceled) => next.run(request).await,
+            };
+        }
+
+        let guard = InFlight {
+            in_flight: &self.in_flight,
+            key,
+        };
+        let result = next.run(request).await;
+        for waiter in guard.finish() {
+            let _ = waiter.send(share(&result));
+        }
+        result
+    }
+}
+
+impl Interceptor for Deduplicate {
+    fn intercept<'a>(&'a self, request: Request, next: Next<'a>) -> SendFuture<'a> {
+        Box::pin(self.send(request, next))
+    }
+}
+
+impl fmt::Debug for Deduplicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Deduplicate")
+            .field("in_flight", &self.in_flight.borrow().len())
+            .finish()
+    }
+}
+
+/// Removes a request from the in-flight requests when it completes or is dropped.
+struct InFlight<'a> {
+    in_flight: &'a RefCell<HashMap<String, Waiters>>,
+    key: String,
+}
+
+impl InFlight<'_> {
+    /// Returns the requests waiting for the result.
+    fn finish(self) -> Waiters {
+        self.in_flight
+            .borrow_mut()
+            .remove(&self.key)
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        // Dropping the senders wakes up the waiting requests, if `finish` was not called.
+        self.in_flight.borrow_mut().remove(&self.key);
+    }
+}
+
+/// Identifies requests which can share a response.
+fn request_key(request: &Request) -> String {
+    let mut key = request.url();
+    for (name, value) in request.headers().entries() {
+        // Every traced request carries a new `traceparent`, which does not change the response.
+        if name.eq_ignore_ascii_case("traceparent") {
+            continue;
+        }
+        key.push('\n');
+        key.push_str(&name);
+        key.push(':');
+        key.push_str(&value);
+    }
+    key
+}
+
+/// Copies `result` for another request waiting for it.
+fn share(result: &Result<Response, Error>) -> Result<Response, Error> {
+    match result {
+        Ok(response) => response.try_clone(),
+        Err(error) => Err(duplicate(error)),
+    }
+}
+
+/// Copies `error`. Underlying errors, which cannot be cloned, are replaced by their message, as
+/// are the variants which requests do not fail with.
+fn duplicate(error: &Error) -> Error {
+    match error {
+        Error::JsError(error) => {
+            let copy = js_sys::Error::new(&error.message);
+            copy.set_name(&error.name);
+            Error::JsError(JsError::from(copy))
+        }
+        Error::Network {
+            method,
+            url,
+            source,
+        } => Error::Network {
+            method: method.clone(),
+            url: url.clone(),
+            source: source.to_string().into(),
+        },
+        Error::Aborted { method, url } => Error::Aborted {
+            method: method.clone(),
+            url: url.clone(),
+        },
+        Error::Timeout(timeout) => Error::Timeout(*timeout),
+        Error::Status {
+            url,
+            status,
+            status_text,
+        } => Error::Status {
+            url: url.clone(),
+            status: *status,
+            status_text: status_text.clone(),
+        },
+        Error::BodyUsed { url } => Error::BodyUsed { url: url.clone() },
+        Error::Decode { url, source } => Error::Decode {
+            url: url.clone(),
+            source: source.to_string().into(),
+        },
+        Error::FetchUnavailable => Error::FetchUnavailable,
+        error => Error::GlooError(error.to_string()),
+    }
+}
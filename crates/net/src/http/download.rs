@@ -0,0 +1,328 @@
+use crate::http::request::clamped_millis;
+use crate::http::{Progress, Request, Response, RetryPolicy, Transport};
+use crate::{js_to_error, js_to_js_error, Error};
+use gloo_timers::future::TimeoutFuture;
+use http::Method;
+use std::fmt;
+use std::rc::Rc;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+/// Downloads a resource in chunks with `Range` requests and assembles it into a
+/// [`gloo_file::Blob`].
+///
+/// Every chunk is checked against the `Content-Range` header of its response. When a request
+/// fails, or its body is cut short, the download resumes from the last received byte according to
+/// the [`RetryPolicy`]; the number of attempts is counted per chunk. Subsequent requests carry
+/// the `ETag` of the first response in an `If-Range` header, and the download fails with
+/// [`Error::ResourceChanged`] if the resource changes in between. Servers which ignore `Range`
+/// are supported as long as the whole resource arrives in the first response.
+///
+/// Chunks are kept as blobs, so the browser may store them outside of the WebAssembly memory.
+/// For cross-origin resources, the server has to expose the `Content-Range` and `ETag` headers
+/// with `Access-Control-Expose-Headers`.
+///
+/// # Example
+///
+/// ```
+/// # use gloo_net::http::Download;
+/// # async fn no_run() -> Result<(), gloo_net::Error> {
+/// let video = Download::new("/videos/intro.webm")
+///     .chunk_size(4 * 1024 * 1024)
+///     .on_progress(|progress| log(progress.fraction()))
+///     .send()
+///     .await?;
+/// # Ok(())
+/// # }
+/// # fn log(_: Option<f64>) {}
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "download")))]
+pub struct Download {
+    url: String,
+    headers: Vec<(String, String)>,
+    chunk_size: u64,
+    retry: RetryPolicy,
+    transport: Option<Rc<dyn Transport>>,
+    on_progress: Option<Box<dyn FnMut(Progress)>>,
+}
+
+impl Download {
+    /// Creates a download of `url` in chunks of 1 MiB, retried with the default [`RetryPolicy`].
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            headers: Vec::new(),
+            chunk_size: 1024 * 1024,
+            retry: RetryPolicy::default(),
+            transport: None,
+            on_progress: None,
+        }
+    }
+
+    /// Sets a header on every request of the download.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// The number of bytes to request at once. A `chunk_size` of `0` is treated as `1`.
+    pub fn chunk_size(mut self, chunk_size: u64) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Sets how often, and after which delay, a failed chunk is requested again.
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Sends the requests with the given transport instead of `fetch`.
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Rc::new(transport));
+        self
+    }
+
+    /// Calls `on_progress` after every received chunk.
+    ///
+    /// The total is taken from the `Content-Range` header, if the server reports it.
+    pub fn on_progress(mut self, on_progress: impl FnMut(Progress) + 'static) -> Self {
+        self.on_progress = Some(Box::new(on_progress));
+        self
+    }
+
+    /// Downloads the resource.
+    ///
+    /// The blob has the `Content-Type` of the first response.
+    pub async fn send(mut self) -> Result<gloo_file::Blob, Error> {
+        let max_attempts = self.retry.attempts_for(&Method::GET);
+        let mut state = State::default();
+        let mut attempt = 1;
+        loop {
+            let response = self.request(&state)?.send().await;
+            let retryable = self.retry.should_retry(&response);
+            let result = match response {
+                Ok(response) if !retryable => state.receive(response, self.chunk_size).await,
+                Ok(response) => Err(status_error(&response)),
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(done) => {
+                    attempt = 1;
+                    if let Some(on_progress) = &mut self.on_progress {
+                        on_progress(Progress {
+                            loaded: state.offset,
+                            total: state.total,
+                        });
+                    }
+                    if done {
+                        return state.assemble();
+                    }
+                }
+                Err(e)
+                    if attempt < max_attempts
+                        && (retryable
+                            || matches!(e, Error::Network { .. } | Error::Timeout(_))) =>
+                {
+                    TimeoutFuture::new(clamped_millis(self.retry.backoff_after(attempt))).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// The request for the chunk starting at the current offset.
+    fn request(&self, state: &State) -> Result<Request, Error> {
+        let mut builder = Request::get(&self.url);
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+        let end = state.offset.saturating_add(self.chunk_size - 1);
+        builder = builder.header("Range", &format!("bytes={}-{}", state.offset, end));
+        // Weak validators cannot be used with `If-Range`; those are compared after the fact.
+        if let Some(etag) = state.etag.as_deref().filter(|etag| !etag.starts_with("W/")) {
+            builder = builder.header("If-Range", etag);
+        }
+        builder.shared_transport(self.transport.clone()).build()
+    }
+}
+
+impl fmt::Debug for Download {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only the names of the headers, as their values may be credentials.
+        let headers: Vec<_> = self.headers.iter().map(|(name, _)| name).collect();
+        f.debug_struct("Download")
+            .field("url", &self.url)
+            .field("headers", &headers)
+            .field("chunk_size", &self.chunk_size)
+            .field("retry", &self.retry)
+            .finish_non_exhaustive()
+    }
+}
+
+/// What has been downloaded so far.
+#[derive(Default)]
+struct State {
+    offset: u64,
+    total: Option<u64>,
+    etag: Option<String>,
+    content_type: Option<String>,
+    chunks: Vec<web_sys::Blob>,
+}
+
+impl State {
+    /// Stores the chunk carried by `response`, returning whether the download is complete.
+    async fn receive(&mut self, response: Response, chunk_size: u64) -> Result<bool, Error> {
+        let headers = response.headers();
+        let content_range = headers
+            .get("Content-Range")
+            .and_then(|value| ContentRange::parse(&value));
+        match response.status() {
+            206 => {}
+            // The server ignored the `Range` header and sent the whole resource.
+            200 if self.offset == 0 => {
+                self.content_type = headers.get("Content-Type");
+                let chunk = read_chunk(response).await?;
+                self.offset = chunk.size() as u64;
+                self.total = Some(self.offset);
+                self.chunks.push(chunk);
+                return Ok(true);
+            }
+            // The resource no longer matches `If-Range`, or the server stopped honouring `Range`.
+            200 => return Err(changed(&response.url())),
+            // The previous chunk ended exactly at the end of the resource, or the resource is
+            // empty.
+            416 => {
+                let total = content_range.as_ref().and_then(|range| range.total);
+                let unknown = total.is_none() && self.total.is_none();
+                if total == Some(self.offset) || (unknown && self.offset > 0) {
+                    if self.chunks.is_empty() {
+                        self.content_type = headers.get("Content-Type");
+                    }
+                    return Ok(true);
+                }
+                return Err(status_error(&response));
+            }
+            _ => return Err(status_error(&response)),
+        }
+
+        let url = response.url();
+        let range = match content_range {
+            Some(range) if range.start == self.offset => range,
+            _ => {
+                return Err(Error::Decode {
+                    url,
+                    source: format!(
+                        "unexpected Content-Range, expected bytes from {}",
+                        self.offset
+                    )
+                    .into(),
+                })
+            }
+        };
+        let etag = headers.get("ETag");
+        if self.chunks.is_empty() {
+            self.etag = etag;
+            self.content_type = headers.get("Content-Type");
+        } else if etag != self.etag || (range.total.is_some() && range.total != self.total) {
+            return Err(changed(&url));
+        }
+        self.total = range.total;
+
+        let chunk = read_chunk(response).await?;
+        let received = chunk.size() as u64;
+        let expected = range.end - range.start + 1;
+        if received == 0 || received > expected {
+            return Err(Error::Network {
+                method: Method::GET,
+                url,
+                source: format!("received {received} bytes for a range of {expected}").into(),
+            });
+        }
+        self.offset += received;
+        self.chunks.push(chunk);
+
+        Ok(match self.total {
+            Some(total) => self.offset >= total,
+            // Without a total, the server shortening the range marks the end.
+            None => received == expected && expected < chunk_size,
+        })
+    }
+
+    /// Joins the received chunks into one blob.
+    fn assemble(self) -> Result<gloo_file::Blob, Error> {
+        let chunks: js_sys::Array = self.chunks.into_iter().collect();
+        let mut options = web_sys::BlobPropertyBag::new();
+        if let Some(content_type) = &self.content_type {
+            options.type_(content_type);
+        }
+        let blob = web_sys::Blob::new_with_blob_sequence_and_options(&chunks, &options)
+            .map_err(js_to_error)?;
+        Ok(blob.into())
+    }
+}
+
+/// Reads the body of `response` into a blob.
+///
+/// Failures while reading are reported as network errors, so that the chunk is retried.
+async fn read_chunk(response: Response) -> Result<web_sys::Blob, Error> {
+    let url = response.url();
+    let raw = web_sys::Response::from(response);
+    let blob = async {
+        let blob = JsFuture::from(raw.blob()?).await?;
+        Ok::<web_sys::Blob, JsValue>(blob.unchecked_into())
+    };
+    blob.await.map_err(|error| Error::Network {
+        method: Method::GET,
+        url,
+        source: Box::new(js_to_js_error(error)),
+    })
+}
+
+/// A parsed `Content-Range: bytes <start>-<end>/<total>` header.
+struct ContentRange {
+    start: u64,
+    end: u64,
+    total: Option<u64>,
+}
+
+impl ContentRange {
+    /// Parses the header, where `total` may be `*`. Unsatisfied ranges, `bytes */<total>`, are
+    /// parsed as an empty range.
+    fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().strip_prefix("bytes ")?;
+        let (range, total) = value.split_once('/')?;
+        let total = match total.trim() {
+            "*" => None,
+            total => Some(total.parse().ok()?),
+        };
+        if range.trim() == "*" {
+            return Some(Self {
+                start: 0,
+                end: 0,
+                total,
+            });
+        }
+        let (start, end) = range.split_once('-')?;
+        let start: u64 = start.trim().parse().ok()?;
+        let end: u64 = end.trim().parse().ok()?;
+        (start <= end).then_some(Self { start, end, total })
+    }
+}
+
+/// The error for a response with an unexpected status.
+fn status_error(response: &Response) -> Error {
+    Error::Status {
+        url: response.url(),
+        status: response.status(),
+        status_text: response.status_text(),
+    }
+}
+
+/// The error for a resource which changed while it was being downloaded.
+fn changed(url: &str) -> Error {
+    Error::ResourceChanged {
+        url: url.to_string(),
+    }
+}
@@ -0,0 +1,328 @@
+use crate::http::{Headers, Interceptor, Next, QueryParams, Request, Response, SendFuture};
+use crate::Error;
+use js_sys::Date;
+use serde_json::{json, Value};
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+/// The value recorded in place of redacted headers, query parameters and bodies.
+const REDACTED: &str = "[redacted]";
+
+/// An [`Interceptor`] which records the requests and responses passing through it in the
+/// [HAR 1.2](http://www.softwareishard.com/blog/har-12-spec/) format, e.g. to attach them to a
+/// support ticket.
+///
+/// Every entry has the headers and query parameters of the request and response, and how long it
+/// took until the response headers arrived. The `Authorization`, `Proxy-Authorization`, `Cookie`
+/// and `Set-Cookie` headers are redacted; [`redact_header`](Self::redact_header) and
+/// [`redact_query`](Self::redact_query) add more. Failed requests are recorded with status `0`
+/// and the error, with the query parameters of the URL redacted, in the `_error` field of the
+/// response.
+///
+/// Bodies are only recorded with [`record_bodies`](Self::record_bodies). Responses are then read
+/// to the end before they are returned, which delays streaming them.
+///
+/// Clones share their entries, so keep a clone around to export them after handing one to a
+/// [`Client`](super::Client).
+///
+/// # Example
+///
+/// ```
+/// # use gloo_net::http::{Client, HarRecorder};
+/// # async fn no_run() -> Result<(), gloo_net::Error> {
+/// let recorder = HarRecorder::new().redact_query("api_key");
+/// let client = Client::builder().interceptor(recorder.clone()).build();
+/// client.get("/users/1").send().await?;
+///
+/// // A URL to download the recording from, e.g. with `<a href=".." download="session.har">`.
+/// let url = gloo_file::ObjectUrl::from(recorder.to_blob());
+/// # Ok(())
+/// # }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "har")))]
+#[derive(Clone)]
+pub struct HarRecorder {
+    state: Rc<RefCell<State>>,
+}
+
+type RedactBody = dyn Fn(&str) -> String;
+
+struct State {
+    record_bodies: bool,
+    redacted_headers: Vec<String>,
+    redacted_query: Vec<String>,
+    redact_body: Option<Rc<RedactBody>>,
+    entries: Vec<Value>,
+}
+
+impl HarRecorder {
+    /// Creates a recorder without any entries, which does not record bodies.
+    pub fn new() -> Self {
+        let redacted_headers = [
+            "authorization",
+            "proxy-authorization",
+            "cookie",
+            "set-cookie",
+        ];
+        Self {
+            state: Rc::new(RefCell::new(State {
+                record_bodies: false,
+                redacted_headers: redacted_headers
+                    .iter()
+                    .map(|name| name.to_string())
+                    .collect(),
+                redacted_query: Vec::new(),
+                redact_body: None,
+                entries: Vec::new(),
+            })),
+        }
+    }
+
+    /// Sets whether the bodies of requests and responses are recorded, as text.
+    pub fn record_bodies(self, record_bodies: bool) -> Self {
+        self.state.borrow_mut().record_bodies = record_bodies;
+        self
+    }
+
+    /// Replaces the value of the header `name` with `[redacted]`, in requests and responses.
+    pub fn redact_header(self, name: &str) -> Self {
+        let name = name.to_ascii_lowercase();
+        self.state.borrow_mut().redacted_headers.push(name);
+        self
+    }
+
+    /// Replaces the values of the query parameter `name` with `[redacted]`, in the URL and the
+    /// query string of the request.
+    pub fn redact_query(self, name: &str) -> Self {
+        self.state
+            .borrow_mut()
+            .redacted_query
+            .push(name.to_string());
+        self
+    }
+
+    /// Passes recorded bodies through `redact` before they are stored, e.g. to remove passwords
+    /// from JSON payloads.
+    pub fn redact_body(self, redact: impl Fn(&str) -> String + 'static) -> Self {
+        self.state.borrow_mut().redact_body = Some(Rc::new(redact));
+        self
+    }
+
+    /// The number of recorded entries.
+    pub fn len(&self) -> usize {
+        self.state.borrow().entries.len()
+    }
+
+    /// Whether no entries have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all recorded entries.
+    pub fn clear(&self) {
+        self.state.borrow_mut().entries.clear();
+    }
+
+    /// The recorded entries as a HAR log.
+    pub fn to_har(&self) -> Value {
+        json!({
+            "log": {
+                "version": "1.2",
+                "creator": {
+                    "name": "gloo-net",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+                "entries": self.state.borrow().entries,
+            }
+        })
+    }
+
+    /// The recorded entries as a HAR file, with the `application/json` MIME type.
+    pub fn to_blob(&self) -> gloo_file::Blob {
+        gloo_file::Blob::new_with_options(
+            self.to_har().to_string().as_str(),
+            Some("application/json"),
+        )
+    }
+
+    async fn send(&self, request: Request, next: Next<'_>) -> Result<Response, Error> {
+        let started = Date::now();
+        let mut entry = json!({
+            "startedDateTime": String::from(Date::new(&started.into()).to_iso_string()),
+            "request": self.request_entry(&request).await,
+            "cache": {},
+        });
+
+        let url = request.url();
+        let result = next.run(request).await;
+        let wait = Date::now() - started;
+        entry["response"] = match &result {
+            Ok(response) => self.response_entry(response).await,
+            Err(error) => self.error_entry(error, &url),
+        };
+        let receive = Date::now() - started - wait;
+        entry["time"] = json!(wait + receive);
+        entry["timings"] = json!({
+            "send": 0,
+            "wait": wait,
+            "receive": receive,
+        });
+
+        self.state.borrow_mut().entries.push(entry);
+        result
+    }
+
+    async fn request_entry(&self, request: &Request) -> Value {
+        let (url, query) = self.redact_url(&request.url());
+        let headers = request.headers();
+        let mut entry = json!({
+            "method": request.method().as_str(),
+            "url": url,
+            "httpVersion": "HTTP/1.1",
+            "cookies": [],
+            "headers": self.headers(&headers),
+            "queryString": query,
+            "headersSize": -1,
+            "bodySize": -1,
+        });
+        let copy = (self.records_bodies() && request.body().is_some()).then(|| request.try_clone());
+        let text = match copy {
+            Some(Ok(copy)) => copy.text().await.ok(),
+            _ => None,
+        };
+        if let Some(text) = text.map(|text| self.redacted_body(text)) {
+            entry["bodySize"] = json!(text.len());
+            entry["postData"] = json!({
+                "mimeType": headers.get("Content-Type").unwrap_or_default(),
+                "text": text,
+            });
+        }
+        entry
+    }
+
+    async fn response_entry(&self, response: &Response) -> Value {
+        let headers = response.headers();
+        let length = headers
+            .get("Content-Length")
+            .and_then(|length| length.parse::<i64>().ok());
+        let mut content = json!({
+            "size": length.unwrap_or(0),
+            "mimeType": headers.get("Content-Type").unwrap_or_default(),
+        });
+        let text = match self.records_bodies().then(|| response.try_clone()) {
+            Some(Ok(copy)) => copy.text().await.ok(),
+            _ => None,
+        };
+        if let Some(text) = text.map(|text| self.redacted_body(text)) {
+            content["size"] = json!(text.len());
+            content["text"] = json!(text);
+        }
+        json!({
+            "status": response.status(),
+            "statusText": response.status_text(),
+            "httpVersion": "HTTP/1.1",
+            "cookies": [],
+            "headers": self.headers(&headers),
+            "content": content,
+            "redirectURL": headers.get("Location").unwrap_or_default(),
+            "headersSize": -1,
+            "bodySize": length.unwrap_or(-1),
+        })
+    }
+
+    fn records_bodies(&self) -> bool {
+        self.state.borrow().record_bodies
+    }
+
+    fn redacted_body(&self, text: String) -> String {
+        let redact = self.state.borrow().redact_body.clone();
+        match redact {
+            Some(redact) => redact(&text),
+            None => text,
+        }
+    }
+
+    fn headers(&self, headers: &Headers) -> Value {
+        let state = self.state.borrow();
+        let headers: Vec<_> = headers
+            .entries()
+            .map(|(name, value)| {
+                let redacted = state.redacted_headers.contains(&name.to_ascii_lowercase());
+                let value = if redacted {
+                    REDACTED.to_string()
+                } else {
+                    value
+                };
+                json!({ "name": name, "value": value })
+            })
+            .collect();
+        json!(headers)
+    }
+
+    /// Redacts the query parameters of `url`, returning the new URL and its query string.
+    fn redact_url(&self, url: &str) -> (String, Value) {
+        let parsed = match web_sys::Url::new(url) {
+            Ok(parsed) => parsed,
+            Err(_) => return (url.to_string(), json!([])),
+        };
+        let state = self.state.borrow();
+        let query = QueryParams::new();
+        let mut pairs = Vec::new();
+        for (name, value) in QueryParams::from_raw(parsed.search_params()).iter() {
+            let value = if state.redacted_query.contains(&name) {
+                REDACTED.to_string()
+            } else {
+                value
+            };
+            query.append(&name, &value);
+            pairs.push(json!({ "name": name, "value": value }));
+        }
+        if !state.redacted_query.is_empty() && !pairs.is_empty() {
+            parsed.set_search(&query.to_string());
+        }
+        (parsed.href(), json!(pairs))
+    }
+
+    /// The response entry of a request to `url` which failed.
+    fn error_entry(&self, error: &Error, url: &str) -> Value {
+        // Error messages quote the URL of the request.
+        let (redacted, _) = self.redact_url(url);
+        json!({
+            "status": 0,
+            "statusText": "",
+            "httpVersion": "",
+            "cookies": [],
+            "headers": [],
+            "content": { "size": 0, "mimeType": "" },
+            "redirectURL": "",
+            "headersSize": -1,
+            "bodySize": -1,
+            "_error": error.to_string().replace(url, &redacted),
+        })
+    }
+}
+
+impl Default for HarRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Interceptor for HarRecorder {
+    fn intercept<'a>(&'a self, request: Request, next: Next<'a>) -> SendFuture<'a> {
+        Box::pin(self.send(request, next))
+    }
+}
+
+impl fmt::Debug for HarRecorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.borrow();
+        f.debug_struct("HarRecorder")
+            .field("record_bodies", &state.record_bodies)
+            .field("redacted_headers", &state.redacted_headers)
+            .field("redacted_query", &state.redacted_query)
+            .field("entries", &state.entries.len())
+            .finish_non_exhaustive()
+    }
+}
@@ -1,5 +1,9 @@
+use crate::http::typed_headers::{self, TypedHeader};
+use crate::Error;
 use gloo_utils::iter::UncheckedIter;
+use http::header::{HeaderMap, HeaderName, HeaderValue};
 use js_sys::{Array, Map};
+use std::convert::TryFrom;
 use std::fmt;
 use wasm_bindgen::{JsCast, UnwrapThrowExt};
 
@@ -58,6 +62,37 @@ impl Headers {
         self.raw.has(name).unwrap_throw()
     }
 
+    /// Gets a header if it is present, parsed as the [`TypedHeader`] `H`.
+    ///
+    /// Fails with [`Error::InvalidHeader`] if the value cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use gloo_net::http::{ContentLength, Headers};
+    /// # fn no_run() -> Result<(), gloo_net::Error> {
+    /// let headers = Headers::new();
+    /// headers.set("Content-Length", "1024");
+    /// assert_eq!(headers.typed_get::<ContentLength>()?, Some(ContentLength(1024)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn typed_get<H: TypedHeader>(&self) -> Result<Option<H>, Error> {
+        self.get(H::NAME)
+            .map(|value| typed_headers::decode(&value))
+            .transpose()
+    }
+
+    /// Overwrites the header `H` with the formatted `header`.
+    ///
+    /// Fails with [`Error::InvalidHeader`] if `header` cannot be formatted as a valid header
+    /// value, in which case the headers are left unchanged.
+    pub fn typed_set<H: TypedHeader>(&self, header: &H) -> Result<(), Error> {
+        let value = typed_headers::encode(header)?;
+        self.set(H::NAME, &value);
+        Ok(())
+    }
+
     /// Overwrites a header with the given name.
     pub fn set(&self, name: &str, value: &str) {
         self.raw.set(name, value).unwrap_throw()
@@ -93,6 +128,48 @@ impl Headers {
     }
 }
 
+/// Fails with [`Error::InvalidHeader`] if a header value contains characters other than visible
+/// ASCII, which `fetch` would decode differently.
+impl TryFrom<&HeaderMap> for Headers {
+    type Error = Error;
+
+    fn try_from(map: &HeaderMap) -> Result<Self, Self::Error> {
+        let headers = Headers::new();
+        for (name, value) in map {
+            let value = value.to_str().map_err(|source| Error::InvalidHeader {
+                name: name.to_string(),
+                source: Box::new(source),
+            })?;
+            headers.append(name.as_str(), value);
+        }
+        Ok(headers)
+    }
+}
+
+/// Fails with [`Error::InvalidHeader`] if a header name or value is not accepted by
+/// [`HeaderName`] or [`HeaderValue`].
+impl TryFrom<&Headers> for HeaderMap {
+    type Error = Error;
+
+    fn try_from(headers: &Headers) -> Result<Self, Self::Error> {
+        let mut map = HeaderMap::new();
+        for (name, value) in headers.entries() {
+            let header_name =
+                HeaderName::try_from(name.as_str()).map_err(|source| Error::InvalidHeader {
+                    name: name.clone(),
+                    source: Box::new(source),
+                })?;
+            let value =
+                HeaderValue::try_from(value.as_str()).map_err(|source| Error::InvalidHeader {
+                    name,
+                    source: Box::new(source),
+                })?;
+            map.append(header_name, value);
+        }
+        Ok(map)
+    }
+}
+
 impl fmt::Debug for Headers {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut dbg = f.debug_struct("Headers");
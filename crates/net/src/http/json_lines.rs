@@ -0,0 +1,101 @@
+use crate::http::BodyStream;
+use crate::Error;
+use futures_core::Stream;
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A body of newline-delimited JSON, decoded as a [`Stream`] of records.
+///
+/// Created by [`Response::json_lines`](super::Response::json_lines). Every record is decoded as
+/// soon as its terminating newline arrives; records may be split across any number of chunks.
+/// Blank lines are skipped and the last record does not need a trailing newline.
+///
+/// A record which fails to decode yields an [`Error::Decode`], after which the following records
+/// are still decoded.
+pub struct JsonLines<T> {
+    inner: BodyStream,
+    url: String,
+    buffer: Vec<u8>,
+    /// How much of `buffer` is known not to contain a newline.
+    searched: usize,
+    done: bool,
+    record: PhantomData<fn() -> T>,
+}
+
+impl<T> JsonLines<T> {
+    pub(crate) fn new(inner: BodyStream, url: String) -> Self {
+        Self {
+            inner,
+            url,
+            buffer: Vec::new(),
+            searched: 0,
+            done: false,
+            record: PhantomData,
+        }
+    }
+
+    /// Takes the next complete line out of the buffer, including its newline.
+    fn next_line(&mut self) -> Option<Vec<u8>> {
+        match self.buffer[self.searched..]
+            .iter()
+            .position(|&b| b == b'\n')
+        {
+            Some(offset) => {
+                let end = self.searched + offset;
+                self.searched = 0;
+                Some(self.buffer.drain(..=end).collect())
+            }
+            None if self.done && !self.buffer.is_empty() => {
+                self.searched = 0;
+                Some(mem::take(&mut self.buffer))
+            }
+            None => {
+                self.searched = self.buffer.len();
+                None
+            }
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Stream for JsonLines<T> {
+    type Item = Result<T, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(line) = self.next_line() {
+                if line.iter().all(u8::is_ascii_whitespace) {
+                    continue;
+                }
+                let record = serde_json::from_slice(&line).map_err(|error| Error::Decode {
+                    url: self.url.clone(),
+                    source: Box::new(error),
+                });
+                return Poll::Ready(Some(record));
+            }
+            if self.done {
+                return Poll::Ready(None);
+            }
+
+            match Pin::new(&mut self.inner).poll_next(cx) {
+                Poll::Ready(Some(Ok(chunk))) => self.buffer.extend_from_slice(&chunk),
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => self.done = true,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for JsonLines<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsonLines")
+            .field("inner", &self.inner)
+            .field("url", &self.url)
+            .field("buffered", &self.buffer.len())
+            .finish()
+    }
+}
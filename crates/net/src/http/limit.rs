@@ -0,0 +1,137 @@
+use crate::http::{Interceptor, Next, Request, Response, SendFuture};
+use crate::{js_to_error, Error};
+use futures_channel::oneshot;
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+/// An [`Interceptor`] which caps the number of concurrent requests to the same origin.
+///
+/// Requests beyond the limit wait in a queue and are sent in order as earlier requests complete.
+/// A request stops counting towards the limit once its response headers have arrived; reading
+/// the body is not limited. Dropping a queued request removes it from the queue.
+///
+/// # Example
+///
+/// ```
+/// # use gloo_net::http::{Client, ConcurrencyLimit};
+/// let client = Client::builder()
+///     .interceptor(ConcurrencyLimit::per_origin(6))
+///     .build();
+/// ```
+pub struct ConcurrencyLimit {
+    max: usize,
+    origins: RefCell<HashMap<String, Origin>>,
+}
+
+#[derive(Default)]
+struct Origin {
+    active: usize,
+    queue: VecDeque<oneshot::Sender<()>>,
+}
+
+impl ConcurrencyLimit {
+    /// Allows up to `max` concurrent requests per origin. A `max` of `0` is treated as `1`.
+    pub fn per_origin(max: usize) -> Self {
+        Self {
+            max: max.max(1),
+            origins: RefCell::new(HashMap::new()),
+        }
+    }
+
+    async fn send(&self, request: Request, next: Next<'_>) -> Result<Response, Error> {
+        let origin = web_sys::Url::new(&request.url())
+            .map_err(js_to_error)?
+            .origin();
+        let _permit = self.acquire(origin).await;
+        next.run(request).await
+    }
+
+    async fn acquire(&self, origin: String) -> Permit<'_> {
+        let receiver = {
+            let mut origins = self.origins.borrow_mut();
+            let state = origins.entry(origin.clone()).or_default();
+            if state.active < self.max {
+                state.active += 1;
+                None
+            } else {
+                let (sender, receiver) = oneshot::channel();
+                state.queue.push_back(sender);
+                Some(receiver)
+            }
+        };
+        if let Some(receiver) = receiver {
+            let mut waiting = Waiting {
+                limit: self,
+                origin: &origin,
+                receiver,
+            };
+            // Senders are only dropped after handing over the slot, or together with `self`.
+            let _ = (&mut waiting.receiver).await;
+        }
+        Permit {
+            limit: self,
+            origin,
+        }
+    }
+
+    /// Hands the slot of a completed request to the next queued request of `origin`.
+    fn release(&self, origin: &str) {
+        let mut origins = self.origins.borrow_mut();
+        let state = match origins.get_mut(origin) {
+            Some(state) => state,
+            None => return,
+        };
+        while let Some(next) = state.queue.pop_front() {
+            if next.send(()).is_ok() {
+                return;
+            }
+        }
+        state.active -= 1;
+        if state.active == 0 {
+            origins.remove(origin);
+        }
+    }
+}
+
+impl Interceptor for ConcurrencyLimit {
+    fn intercept<'a>(&'a self, request: Request, next: Next<'a>) -> SendFuture<'a> {
+        Box::pin(self.send(request, next))
+    }
+}
+
+impl fmt::Debug for ConcurrencyLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConcurrencyLimit")
+            .field("max", &self.max)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A slot for one request, released on drop.
+struct Permit<'a> {
+    limit: &'a ConcurrencyLimit,
+    origin: String,
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        self.limit.release(&self.origin);
+    }
+}
+
+/// A request waiting in the queue.
+struct Waiting<'a> {
+    limit: &'a ConcurrencyLimit,
+    origin: &'a str,
+    receiver: oneshot::Receiver<()>,
+}
+
+impl Drop for Waiting<'_> {
+    fn drop(&mut self) {
+        // The slot was handed over, but the request was dropped before it could take it.
+        if let Ok(Some(())) = self.receiver.try_recv() {
+            self.limit.release(self.origin);
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use crate::http::{Request, Response, SendFuture, Transport};
+use crate::Error;
+use http::Method;
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+/// An in-memory [`Transport`] which answers requests with canned responses.
+///
+/// Responses are registered with [`on`](Self::on): a request is answered by the first [`Mock`]
+/// whose method, URL and headers match it, and fails with [`Error::GlooError`] if none does. Every
+/// request that reaches the transport is recorded, matched or not.
+///
+/// A mock URL matches the full URL of a request, or, if it starts with `/`, its path and query.
+///
+/// Clones share their mocks and recorded requests, so keep a clone around to make assertions
+/// after handing one to a [`Client`](super::Client) or [`RequestBuilder`](super::RequestBuilder).
+///
+/// # Example
+///
+/// ```
+/// # use gloo_net::http::{Client, Method, MockTransport, Response};
+/// # async fn no_run() -> Result<(), gloo_net::Error> {
+/// let mock = MockTransport::new();
+/// mock.on(Method::GET, "/users/1")
+///     .header("Authorization", "Bearer token")
+///     .respond(Response::builder().json(&serde_json::json!({ "name": "Ferris" }))?);
+///
+/// let client = Client::builder()
+///     .header("Authorization", "Bearer token")
+///     .transport(mock.clone())
+///     .build();
+/// let resp = client.get("/users/1").send().await?;
+/// assert_eq!(resp.status(), 200);
+///
+/// mock.assert_requested(Method::GET, "/users/1");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct MockTransport {
+    state: Rc<RefCell<MockState>>,
+}
+
+#[derive(Default)]
+struct MockState {
+    mocks: Vec<Expectation>,
+    requests: Vec<Request>,
+}
+
+impl MockTransport {
+    /// Creates a transport without any mocks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts mocking requests with the given method and URL.
+    ///
+    /// The mock is only registered once [`Mock::respond`] is called.
+    pub fn on(&self, method: Method, url: &str) -> Mock {
+        Mock {
+            transport: self.clone(),
+            method,
+            url: url.to_string(),
+            headers: Vec::new(),
+        }
+    }
+
+    /// Removes and returns all requests recorded so far.
+    pub fn take_requests(&self) -> Vec<Request> {
+        std::mem::take(&mut self.state.borrow_mut().requests)
+    }
+
+    /// The number of requests recorded so far.
+    pub fn request_count(&self) -> usize {
+        self.state.borrow().requests.len()
+    }
+
+    /// Panics unless a request with the given method and URL has been recorded.
+    ///
+    /// The URL is matched the same way as in [`on`](Self::on).
+    #[track_caller]
+    pub fn assert_requested(&self, method: Method, url: &str) {
+        let state = self.state.borrow();
+        let found = state
+            .requests
+            .iter()
+            .any(|request| request.method() == method && url_matches(url, &request.url()));
+        if !found {
+            let recorded: Vec<_> = state
+                .requests
+                .iter()
+                .map(|request| format!("{} {}", request.method(), request.url()))
+                .collect();
+            panic!(
+                "expected a request to {} {}, recorded requests: {:?}",
+                method, url, recorded
+            );
+        }
+    }
+
+    fn respond_to(&self, request: &Request) -> Result<Response, Error> {
+        let state = self.state.borrow();
+        let mock = state
+            .mocks
+            .iter()
+            .find(|mock| mock.matches(request))
+            .ok_or_else(|| {
+                Error::GlooError(format!(
+                    "no mock matches {} {}",
+                    request.method(),
+                    request.url()
+                ))
+            })?;
+        mock.response.try_clone()
+    }
+}
+
+impl Transport for MockTransport {
+    fn send<'a>(&'a self, request: Request) -> SendFuture<'a> {
+        let response = self.respond_to(&request);
+        self.state.borrow_mut().requests.push(request);
+        Box::pin(async move { response })
+    }
+}
+
+impl fmt::Debug for MockTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.borrow();
+        f.debug_struct("MockTransport")
+            .field("mocks", &state.mocks)
+            .field("requests", &state.requests)
+            .finish()
+    }
+}
+
+/// A canned response of a [`MockTransport`], created by [`MockTransport::on`].
+pub struct Mock {
+    transport: MockTransport,
+    method: Method,
+    url: String,
+    headers: Vec<(String, String)>,
+}
+
+/// A registered [`Mock`].
+#[derive(Debug)]
+struct Expectation {
+    method: Method,
+    url: String,
+    headers: Vec<(String, String)>,
+    response: Response,
+}
+
+impl Mock {
+    /// Only match requests which have this header set to `value`.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Answers matching requests with a copy of `response`.
+    ///
+    /// `response` must not have had its body read.
+    pub fn respond(self, response: Response) {
+        self.transport.state.borrow_mut().mocks.push(Expectation {
+            method: self.method,
+            url: self.url,
+            headers: self.headers,
+            response,
+        });
+    }
+}
+
+impl Expectation {
+    fn matches(&self, request: &Request) -> bool {
+        if request.method() != self.method || !url_matches(&self.url, &request.url()) {
+            return false;
+        }
+        let headers = request.headers();
+        self.headers
+            .iter()
+            .all(|(name, value)| headers.get(name).as_deref() == Some(value.as_str()))
+    }
+}
+
+impl fmt::Debug for Mock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mock")
+            .field("method", &self.method)
+            .field("url", &self.url)
+            .field("headers", &self.headers)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Whether the mock URL `pattern` matches the absolute URL `url`.
+fn url_matches(pattern: &str, url: &str) -> bool {
+    if pattern == url {
+        return true;
+    }
+    if !pattern.starts_with('/') {
+        return false;
+    }
+    match web_sys::Url::new(url) {
+        Ok(url) => format!("{}{}", url.pathname(), url.search()) == pattern,
+        Err(_) => false,
+    }
+}
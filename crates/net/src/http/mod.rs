@@ -1,5 +1,8 @@
 //! Wrapper around the `fetch` API.
 //!
+//! With the `native` feature on targets other than `wasm32`, [`native`] provides the core types
+//! on top of `reqwest` as well, to send requests outside of the browser.
+//!
 //! # Example
 //!
 //! ```
@@ -13,15 +16,68 @@
 //! # }
 //! ```
 
+mod cache;
+mod client;
+#[cfg(feature = "serde")]
+mod codec;
+#[cfg(feature = "compression")]
+mod compression;
+mod dedupe;
+#[cfg(feature = "download")]
+mod download;
+#[cfg(feature = "har")]
+mod har;
 mod headers;
+#[cfg(feature = "json")]
+mod json_lines;
+mod limit;
+mod mock;
+mod multipart;
+#[cfg(all(feature = "native", not(target_arch = "wasm32")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "native")))]
+pub mod native;
+mod progress;
 mod query;
+#[cfg(feature = "serde")]
+mod query_serializer;
 mod request;
 mod response;
+mod retry;
+mod stream;
+#[cfg(feature = "tracing")]
+mod trace;
+mod transport;
+mod typed_headers;
+mod url;
+mod xhr;
 
+pub use cache::{CachePolicy, ResponseCache};
+pub use client::{Client, ClientBuilder, Interceptor, Next};
+#[cfg(feature = "serde")]
+pub use codec::*;
+#[cfg(feature = "compression")]
+pub use compression::ContentEncoding;
+pub use dedupe::Deduplicate;
+#[cfg(feature = "download")]
+pub use download::Download;
+#[cfg(feature = "har")]
+pub use har::HarRecorder;
 pub use headers::Headers;
 #[doc(inline)]
 pub use http::Method;
+#[cfg(feature = "json")]
+pub use json_lines::JsonLines;
+pub use limit::ConcurrencyLimit;
+pub use mock::{Mock, MockTransport};
+pub use multipart::{Multipart, Part};
+pub use progress::{Progress, ProgressStream};
 pub use query::QueryParams;
 
 pub use request::{Request, RequestBuilder};
 pub use response::{IntoRawResponse, Response, ResponseBuilder};
+pub use retry::RetryPolicy;
+pub use stream::BodyStream;
+pub use transport::{FetchTransport, SendFuture, Transport};
+pub use typed_headers::*;
+pub use url::Url;
+pub use xhr::XhrRequest;
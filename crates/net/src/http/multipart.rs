@@ -0,0 +1,237 @@
+use crate::{js_to_error, Error};
+use gloo_utils::iter::UncheckedIter;
+use js_sys::{Array, ArrayBuffer, Map, Uint8Array};
+use std::fmt;
+use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Blob, File, FormData};
+
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+use serde::de::DeserializeOwned;
+
+/// A `multipart/form-data` body, wrapping [`web_sys::FormData`].
+///
+/// Use it as a request body with [`RequestBuilder::multipart`](super::RequestBuilder::multipart),
+/// or read one with [`Request::multipart`](super::Request::multipart) and
+/// [`Response::multipart`](super::Response::multipart).
+///
+/// Blob parts accept anything that can be viewed as a [`web_sys::Blob`], including
+/// `gloo_file::Blob` and `gloo_file::File`.
+///
+/// # Example
+///
+/// ```
+/// # use gloo_net::http::{Multipart, Request};
+/// # async fn no_run(avatar: web_sys::File) -> Result<(), gloo_net::Error> {
+/// let form = Multipart::new()
+///     .text("name", "Ferris")
+///     .file("avatar", &avatar, "ferris.png", Some("image/png"));
+/// let resp = Request::post("/profile").multipart(form)?.send().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Multipart {
+    raw: FormData,
+}
+
+impl Default for Multipart {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Multipart {
+    /// Creates an empty form.
+    pub fn new() -> Self {
+        // pretty sure this will never throw.
+        Self {
+            raw: FormData::new().unwrap_throw(),
+        }
+    }
+
+    /// Build [`Multipart`] from [`web_sys::FormData`].
+    pub fn from_raw(raw: FormData) -> Self {
+        Self { raw }
+    }
+
+    /// Convert [`Multipart`] to [`web_sys::FormData`].
+    pub fn into_raw(self) -> FormData {
+        self.raw
+    }
+
+    /// Appends a text field.
+    pub fn text(self, name: &str, value: &str) -> Self {
+        self.raw.append_with_str(name, value).unwrap_throw();
+        self
+    }
+
+    /// Appends `value` serialized as JSON, with the `application/json` MIME type.
+    ///
+    /// `FormData` can only attach a MIME type to file parts, so the part is sent with the
+    /// filename `blob`.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn json<T: serde::Serialize + ?Sized>(self, name: &str, value: &T) -> Result<Self, Error> {
+        let json = serde_json::to_string(value)?;
+        let mut options = web_sys::BlobPropertyBag::new();
+        options.type_("application/json");
+        let blob = Blob::new_with_str_sequence_and_options(&Array::of1(&json.into()), &options)
+            .map_err(js_to_error)?;
+        Ok(self.blob(name, blob))
+    }
+
+    /// Appends a blob part.
+    ///
+    /// The part uses the MIME type of the blob, and the name of the file if `blob` is a
+    /// [`File`]. Other blobs are sent with the filename `blob`.
+    pub fn blob(self, name: &str, blob: impl AsRef<Blob>) -> Self {
+        self.raw
+            .append_with_blob(name, blob.as_ref())
+            .unwrap_throw();
+        self
+    }
+
+    /// Appends a file part with the given `filename`.
+    ///
+    /// When `mime_type` is `None`, the MIME type of the blob is used.
+    pub fn file(
+        self,
+        name: &str,
+        blob: impl AsRef<Blob>,
+        filename: &str,
+        mime_type: Option<&str>,
+    ) -> Self {
+        let blob = blob.as_ref();
+        let retyped;
+        let blob = match mime_type {
+            Some(mime_type) => {
+                retyped = blob
+                    .slice_with_f64_and_f64_and_content_type(0.0, blob.size(), mime_type)
+                    .unwrap_throw();
+                &retyped
+            }
+            None => blob,
+        };
+        self.raw
+            .append_with_blob_and_filename(name, blob, filename)
+            .unwrap_throw();
+        self
+    }
+
+    /// Gets the first part with the given name.
+    pub fn get(&self, name: &str) -> Option<Part> {
+        Part::from_js(self.raw.get(name))
+    }
+
+    /// Gets all parts with the given name.
+    pub fn get_all(&self, name: &str) -> Vec<Part> {
+        self.raw
+            .get_all(name)
+            .iter()
+            .filter_map(Part::from_js)
+            .collect()
+    }
+
+    /// Whether a part with the given name exists.
+    pub fn has(&self, name: &str) -> bool {
+        self.raw.has(name)
+    }
+
+    /// Iterate over (name, part) pairs.
+    pub fn parts(&self) -> impl Iterator<Item = (String, Part)> {
+        // Here we cheat and cast to a map even though `self` isn't, because the method names match
+        // and everything works.
+        let fake_map: &Map = self.raw.unchecked_ref();
+        UncheckedIter::from(fake_map.entries()).filter_map(|entry| {
+            let entry: Array = entry.unchecked_into();
+            let name = entry.get(0).as_string().unwrap_throw();
+            Part::from_js(entry.get(1)).map(|part| (name, part))
+        })
+    }
+}
+
+impl From<Multipart> for JsValue {
+    fn from(multipart: Multipart) -> Self {
+        multipart.raw.into()
+    }
+}
+
+impl fmt::Debug for Multipart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.parts()).finish()
+    }
+}
+
+/// A single part of a [`Multipart`] form.
+#[derive(Debug, Clone)]
+pub enum Part {
+    /// A text field.
+    Text(String),
+    /// A file field.
+    File(File),
+}
+
+impl Part {
+    fn from_js(value: JsValue) -> Option<Self> {
+        if let Some(text) = value.as_string() {
+            Some(Part::Text(text))
+        } else {
+            value.dyn_into::<File>().ok().map(Part::File)
+        }
+    }
+
+    /// The filename of a file part.
+    pub fn filename(&self) -> Option<String> {
+        match self {
+            Part::Text(_) => None,
+            Part::File(file) => Some(file.name()),
+        }
+    }
+
+    /// The MIME type of a file part, if the browser knows it.
+    pub fn mime_type(&self) -> Option<String> {
+        match self {
+            Part::Text(_) => None,
+            Part::File(file) => Some(file.type_()).filter(|mime_type| !mime_type.is_empty()),
+        }
+    }
+
+    /// Reads the part as a String.
+    pub async fn text(&self) -> Result<String, Error> {
+        match self {
+            Part::Text(text) => Ok(text.clone()),
+            Part::File(file) => {
+                let val = JsFuture::from(file.text()).await.map_err(js_to_error)?;
+                Ok(String::from(&js_sys::JsString::from(val)))
+            }
+        }
+    }
+
+    /// Reads the part as bytes.
+    pub async fn binary(&self) -> Result<Vec<u8>, Error> {
+        match self {
+            Part::Text(text) => Ok(text.clone().into_bytes()),
+            Part::File(file) => {
+                let array_buffer: ArrayBuffer = JsFuture::from(file.array_buffer())
+                    .await
+                    .map_err(js_to_error)?
+                    .unchecked_into();
+                Ok(Uint8Array::new(&array_buffer).to_vec())
+            }
+        }
+    }
+
+    /// Reads the part, parsing it as JSON.
+    ///
+    /// Fails with [`Error::Decode`] if it is not valid JSON. As a part has no URL of its own, the
+    /// URL of the error is empty, like that of a response which was built rather than received.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub async fn json<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        serde_json::from_str::<T>(&self.text().await?).map_err(|e| Error::Decode {
+            url: String::new(),
+            source: Box::new(e),
+        })
+    }
+}
@@ -0,0 +1,181 @@
+use crate::http::typed_headers::{self, TypedHeader};
+use crate::Error;
+use http::header::{HeaderName, HeaderValue};
+use http::HeaderMap;
+use std::cell::RefCell;
+use std::convert::TryFrom;
+use std::fmt;
+
+/// A wrapper around [`http::HeaderMap`].
+///
+/// Like the browser version, invalid header names or values cause a panic.
+pub struct Headers {
+    raw: RefCell<HeaderMap>,
+}
+
+impl Default for Headers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Headers {
+    /// Create a new empty headers object.
+    pub fn new() -> Self {
+        Self::from_raw(HeaderMap::new())
+    }
+
+    /// Build [Headers] from [http::HeaderMap].
+    pub fn from_raw(raw: HeaderMap) -> Self {
+        Self {
+            raw: RefCell::new(raw),
+        }
+    }
+
+    /// Covert [Headers] to [http::HeaderMap].
+    pub fn into_raw(self) -> HeaderMap {
+        self.raw.into_inner()
+    }
+
+    /// This method appends a new value onto an existing header, or adds the header if it does not
+    /// already exist.
+    pub fn append(&self, name: &str, value: &str) {
+        self.raw
+            .borrow_mut()
+            .append(header_name(name), header_value(value));
+    }
+
+    /// Deletes a header if it is present.
+    pub fn delete(&self, name: &str) {
+        self.raw.borrow_mut().remove(name);
+    }
+
+    /// Gets a header if it is present.
+    ///
+    /// Multiple values of the same header are combined, separated by `, `.
+    pub fn get(&self, name: &str) -> Option<String> {
+        let raw = self.raw.borrow();
+        let values: Vec<_> = raw.get_all(name).iter().map(value_to_string).collect();
+        if values.is_empty() {
+            None
+        } else {
+            Some(values.join(", "))
+        }
+    }
+
+    /// Whether a header with the given name exists.
+    pub fn has(&self, name: &str) -> bool {
+        self.raw.borrow().contains_key(name)
+    }
+
+    /// Gets a header if it is present, parsed as the [`TypedHeader`] `H`.
+    ///
+    /// Fails with [`Error::InvalidHeader`] if the value cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use gloo_net::http::native::{ContentLength, Headers};
+    /// let headers = Headers::new();
+    /// headers.set("Content-Length", "1024");
+    /// assert_eq!(headers.typed_get::<ContentLength>()?, Some(ContentLength(1024)));
+    /// # Ok::<(), gloo_net::Error>(())
+    /// ```
+    pub fn typed_get<H: TypedHeader>(&self) -> Result<Option<H>, Error> {
+        self.get(H::NAME)
+            .map(|value| typed_headers::decode(&value))
+            .transpose()
+    }
+
+    /// Overwrites the header `H` with the formatted `header`.
+    ///
+    /// Fails with [`Error::InvalidHeader`] if `header` cannot be formatted as a valid header
+    /// value, in which case the headers are left unchanged.
+    pub fn typed_set<H: TypedHeader>(&self, header: &H) -> Result<(), Error> {
+        let value = typed_headers::encode(header)?;
+        self.set(H::NAME, &value);
+        Ok(())
+    }
+
+    /// Overwrites a header with the given name.
+    pub fn set(&self, name: &str, value: &str) {
+        self.raw
+            .borrow_mut()
+            .insert(header_name(name), header_value(value));
+    }
+
+    /// Iterate over (header name, header value) pairs.
+    ///
+    /// Headers are sorted by name and multiple values of the same header are combined, as in the
+    /// browser.
+    pub fn entries(&self) -> impl Iterator<Item = (String, String)> {
+        let mut names: Vec<_> = self.keys().collect();
+        names.dedup();
+        let entries: Vec<_> = names
+            .into_iter()
+            .map(|name| {
+                let value = self.get(&name).unwrap_or_default();
+                (name, value)
+            })
+            .collect();
+        entries.into_iter()
+    }
+
+    /// Iterate over the names of the headers.
+    pub fn keys(&self) -> impl Iterator<Item = String> {
+        let mut names: Vec<_> = self
+            .raw
+            .borrow()
+            .keys()
+            .map(|name| name.as_str().to_string())
+            .collect();
+        names.sort();
+        names.into_iter()
+    }
+
+    /// Iterate over the values of the headers.
+    pub fn values(&self) -> impl Iterator<Item = String> {
+        self.entries().map(|(_, value)| value)
+    }
+}
+
+fn header_name(name: &str) -> HeaderName {
+    HeaderName::from_bytes(name.as_bytes())
+        .unwrap_or_else(|_| panic!("invalid header name: {:?}", name))
+}
+
+fn header_value(value: &str) -> HeaderValue {
+    HeaderValue::from_str(value).unwrap_or_else(|_| panic!("invalid header value: {:?}", value))
+}
+
+fn value_to_string(value: &HeaderValue) -> String {
+    String::from_utf8_lossy(value.as_bytes()).into_owned()
+}
+
+/// Never fails, but mirrors the conversion of the browser version.
+impl TryFrom<&HeaderMap> for Headers {
+    type Error = Error;
+
+    fn try_from(map: &HeaderMap) -> Result<Self, Self::Error> {
+        Ok(Headers::from_raw(map.clone()))
+    }
+}
+
+/// Never fails, but mirrors the conversion of the browser version.
+impl TryFrom<&Headers> for HeaderMap {
+    type Error = Error;
+
+    fn try_from(headers: &Headers) -> Result<Self, Self::Error> {
+        Ok(headers.raw.borrow().clone())
+    }
+}
+
+impl fmt::Debug for Headers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut dbg = f.debug_struct("Headers");
+        for (key, value) in self.entries() {
+            dbg.field(&key, &value);
+        }
+        dbg.finish()
+    }
+}
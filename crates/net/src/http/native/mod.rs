@@ -0,0 +1,62 @@
+//! HTTP requests sent with [`reqwest`], for use outside of the browser.
+//!
+//! The core types of [`http`](super) have native counterparts here: [`Request`],
+//! [`RequestBuilder`], [`Response`], [`ResponseBuilder`], [`Headers`] and [`QueryParams`]. They
+//! keep the API of their browser counterparts, so API clients written against it can also run
+//! natively, e.g. in command line tools or integration tests, by importing from this module
+//! instead. The typed headers, [`Url`], [`Method`] and with their features the body codecs and
+//! `ContentEncoding` are shared with the browser API and re-exported here.
+//!
+//! Options which only exist in the browser, such as the request cache mode or abort signals,
+//! are not available, and neither are the client, interceptors and transports. The returned
+//! futures need to run inside a [Tokio](https://tokio.rs) runtime.
+//!
+//! # Example
+//!
+//! ```
+//! # use gloo_net::http::native::Request;
+//! # async fn no_run() {
+//! let resp = Request::get("http://localhost:8080/path")
+//!     .send()
+//!     .await
+//!     .unwrap();
+//! assert_eq!(resp.status(), 200);
+//! # }
+//! ```
+
+mod headers;
+mod query;
+mod request;
+mod response;
+// Shared with the browser API, which uses its own `Headers` and `Response` with it.
+#[cfg(feature = "tracing")]
+#[path = "../trace.rs"]
+#[allow(clippy::duplicate_mod)]
+mod trace;
+
+#[cfg(feature = "serde")]
+#[doc(no_inline)]
+pub use super::codec::*;
+#[cfg(feature = "compression")]
+#[doc(no_inline)]
+pub use super::ContentEncoding;
+#[doc(no_inline)]
+pub use super::{typed_headers::*, Method, Url};
+pub use headers::Headers;
+pub use query::QueryParams;
+pub use request::{Request, RequestBuilder};
+pub use response::{Response, ResponseBuilder};
+
+use crate::Error;
+
+fn reqwest_to_error(
+    method: Method,
+    url: String,
+    error: impl std::error::Error + Send + Sync + 'static,
+) -> Error {
+    Error::Network {
+        method,
+        url,
+        source: Box::new(error),
+    }
+}
@@ -0,0 +1,78 @@
+use std::cell::RefCell;
+use std::fmt;
+
+/// A sequence of URL query parameters.
+pub struct QueryParams {
+    raw: RefCell<Vec<(String, String)>>,
+}
+
+impl Default for QueryParams {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl QueryParams {
+    /// Create a new empty query parameters object.
+    pub fn new() -> Self {
+        Self {
+            raw: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Append a parameter to the query string.
+    pub fn append(&self, name: &str, value: &str) {
+        self.raw
+            .borrow_mut()
+            .push((name.to_string(), value.to_string()));
+    }
+
+    /// Get the value of a parameter. If the parameter has multiple occurrences, the first value is
+    /// returned.
+    pub fn get(&self, name: &str) -> Option<String> {
+        self.raw
+            .borrow()
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.clone())
+    }
+
+    /// Get all associated values of a parameter.
+    pub fn get_all(&self, name: &str) -> Vec<String> {
+        self.raw
+            .borrow()
+            .iter()
+            .filter(|(key, _)| key == name)
+            .map(|(_, value)| value.clone())
+            .collect()
+    }
+
+    /// Remove all occurrences of a parameter from the query string.
+    pub fn delete(&self, name: &str) {
+        self.raw.borrow_mut().retain(|(key, _)| key != name);
+    }
+
+    /// Iterate over (name, value) pairs of the query parameters.
+    pub fn iter(&self) -> impl Iterator<Item = (String, String)> {
+        self.raw.borrow().clone().into_iter()
+    }
+}
+
+/// The formatted query parameters ready to be used in a URL query string.
+///
+/// The resulting string does not contain a leading `?` and is encoded as
+/// `application/x-www-form-urlencoded`, like `URLSearchParams` in the browser.
+impl fmt::Display for QueryParams {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let encoded = form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(self.raw.borrow().iter())
+            .finish();
+        f.write_str(&encoded)
+    }
+}
+
+impl fmt::Debug for QueryParams {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,442 @@
+#[cfg(feature = "tracing")]
+use super::trace;
+use super::{reqwest_to_error, Headers, QueryParams, Response};
+#[cfg(feature = "compression")]
+use crate::http::ContentEncoding;
+#[cfg(feature = "serde")]
+use crate::http::{query_serializer, BodyCodec};
+use crate::Error;
+use http::Method;
+use once_cell::sync::Lazy;
+use std::convert::{TryFrom, TryInto};
+use std::fmt;
+use std::time::Duration;
+
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+use serde::de::DeserializeOwned;
+
+/// The client of the requests which are not given one, so connections are pooled.
+static CLIENT: Lazy<reqwest::Client> = Lazy::new(reqwest::Client::new);
+
+/// A builder for an http request, sent with [`reqwest`].
+pub struct RequestBuilder {
+    method: Method,
+    headers: Headers,
+    query: QueryParams,
+    url: String,
+    body: Option<reqwest::Body>,
+    timeout: Option<Duration>,
+    client: Option<reqwest::Client>,
+    #[cfg(feature = "compression")]
+    compression: Option<ContentEncoding>,
+}
+
+impl RequestBuilder {
+    /// Creates a new request that will be sent to `url`.
+    ///
+    /// Uses `GET` by default. Unlike in the browser, `url` has to be absolute. `url` can be
+    /// a `String`, a `&str`, a `Cow<'a, str>`, or a [`Url`](crate::http::Url).
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            method: Method::GET,
+            headers: Headers::new(),
+            query: QueryParams::new(),
+            url: url.into(),
+            body: None,
+            timeout: None,
+            client: None,
+            #[cfg(feature = "compression")]
+            compression: None,
+        }
+    }
+
+    /// Set the body for this request.
+    pub fn body(mut self, body: impl Into<reqwest::Body>) -> Result<Request, Error> {
+        self.body = Some(body.into());
+        self.try_into()
+    }
+
+    /// Replace _all_ the headers.
+    pub fn headers(mut self, headers: Headers) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// Sets a header.
+    pub fn header(self, key: &str, value: &str) -> Self {
+        self.headers.set(key, value);
+        self
+    }
+
+    /// Append query parameters to the url, given as `(name, value)` tuples. Values can be of any
+    /// type that implements [`ToString`].
+    ///
+    /// It is possible to append the same parameters with the same name multiple times, so
+    /// `.query([("a", "1"), ("a", "2")])` results in the query string `a=1&a=2`.
+    pub fn query<'a, T, V>(self, params: T) -> Self
+    where
+        T: IntoIterator<Item = (&'a str, V)>,
+        V: AsRef<str>,
+    {
+        for (name, value) in params {
+            self.query.append(name, value.as_ref());
+        }
+        self
+    }
+
+    /// Append the fields of `params` to the query parameters.
+    ///
+    /// `params` has to serialize as a struct or a map. Nested structs and maps are written with
+    /// brackets, `filter[status]=open`, and sequences repeat their name, `tag=a&tag=b`. Structs
+    /// inside of sequences are numbered, `items[0][id]=1`. Fields which are `None` are left out.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use gloo_net::http::native::Request;
+    /// # use serde::Serialize;
+    /// # fn no_run() -> Result<(), gloo_net::Error> {
+    /// #[derive(Serialize)]
+    /// struct Search<'a> {
+    ///     q: &'a str,
+    ///     tags: Vec<&'a str>,
+    ///     page: Option<u32>,
+    /// }
+    ///
+    /// let search = Search { q: "gloo", tags: vec!["net", "http"], page: None };
+    /// let request = Request::get("/search").serialize_query(&search)?;
+    /// // Result URL: /search?q=gloo&tags=net&tags=http
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn serialize_query<T: serde::Serialize + ?Sized>(self, params: &T) -> Result<Self, Error> {
+        for (name, value) in query_serializer::serialize_query(params)? {
+            self.query.append(&name, &value);
+        }
+        Ok(self)
+    }
+
+    /// A convenience method to set JSON as request body
+    ///
+    /// # Note
+    ///
+    /// This method also sets the `Content-Type` header to `application/json`
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn json<T: serde::Serialize + ?Sized>(self, value: &T) -> Result<Request, Error> {
+        let json = serde_json::to_string(value)?;
+        self.header("Content-Type", "application/json").body(json)
+    }
+
+    /// Serializes `value` with `codec` and sets it as request body.
+    ///
+    /// # Note
+    ///
+    /// This method also sets the `Content-Type` header to the MIME type of `codec`.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn encode<C, T>(self, codec: C, value: &T) -> Result<Request, Error>
+    where
+        C: BodyCodec,
+        T: serde::Serialize + ?Sized,
+    {
+        let body = codec.encode(value).map_err(Error::Encode)?;
+        self.header("Content-Type", codec.content_type()).body(body)
+    }
+
+    /// Adds the MIME type of `codec` to the `Accept` header, to ask the server for a response
+    /// body in that format.
+    ///
+    /// Call this once per supported format, in order of preference, and read the response with
+    /// [`Response::decode_negotiated`].
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn accept<C: BodyCodec>(self, codec: C) -> Self {
+        self.headers.append("Accept", codec.content_type());
+        self
+    }
+
+    /// The request method, e.g., GET, POST.
+    pub fn method(mut self, method: Method) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Aborts the request if no response has been received after `timeout`.
+    ///
+    /// When the timeout elapses [`Request::send`] returns [`Error::Timeout`].
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sends the request with `client` rather than with the client shared by all requests.
+    ///
+    /// The connections of a client are bound to the Tokio runtime which opened them, so use a
+    /// client per runtime if there are several, e.g. in tests.
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Compresses the body with `encoding` and sets the `Content-Encoding` header accordingly.
+    ///
+    /// Fails to build the request if the body is a stream. Only use this with servers that
+    /// accept compressed request bodies.
+    #[cfg(feature = "compression")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "compression")))]
+    pub fn compress(mut self, encoding: ContentEncoding) -> Self {
+        self.compression = Some(encoding);
+        self
+    }
+
+    /// Builds the request and send it to the server, returning the received response.
+    pub async fn send(self) -> Result<Response, Error> {
+        let req: Request = self.try_into()?;
+        req.send().await
+    }
+
+    /// Builds the request.
+    pub fn build(self) -> Result<Request, crate::error::Error> {
+        self.try_into()
+    }
+}
+
+impl TryFrom<RequestBuilder> for Request {
+    type Error = crate::error::Error;
+
+    fn try_from(value: RequestBuilder) -> Result<Self, Self::Error> {
+        let mut url = reqwest::Url::parse(&value.url)
+            .map_err(|e| Error::InvalidUrl(format!("{:?}: {}", value.url, e)))?;
+        let query: Vec<_> = value.query.iter().collect();
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
+        }
+
+        #[cfg(feature = "compression")]
+        let value = compress_body(value)?;
+
+        let mut raw = reqwest::Request::new(value.method, url);
+        *raw.headers_mut() = value.headers.into_raw();
+        *raw.body_mut() = value.body;
+        *raw.timeout_mut() = value.timeout;
+        Ok(Request {
+            raw,
+            client: value.client,
+        })
+    }
+}
+
+/// Compresses the body of `builder`, if [`RequestBuilder::compress`] was used.
+#[cfg(feature = "compression")]
+fn compress_body(mut builder: RequestBuilder) -> Result<RequestBuilder, Error> {
+    let (encoding, body) = match (builder.compression.take(), &builder.body) {
+        (Some(encoding), Some(body)) => (encoding, body),
+        _ => return Ok(builder),
+    };
+    let body = body
+        .as_bytes()
+        .ok_or_else(|| Error::Encode("streaming bodies cannot be compressed".into()))?;
+    let compressed = encoding
+        .encode(body)
+        .map_err(|e| Error::Encode(Box::new(e)))?;
+    builder.body = Some(compressed.into());
+    builder.headers.set("Content-Encoding", encoding.as_str());
+    Ok(builder)
+}
+
+impl fmt::Debug for RequestBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Request").field("url", &self.url).finish()
+    }
+}
+
+/// The [`Request`] sent to the server
+pub struct Request {
+    raw: reqwest::Request,
+    client: Option<reqwest::Client>,
+}
+
+impl Request {
+    /// Creates a new [`GET`][Method::GET] `Request` with url.
+    pub fn get(url: impl Into<String>) -> RequestBuilder {
+        RequestBuilder::new(url).method(Method::GET)
+    }
+
+    /// Creates a new [`POST`][Method::POST] `Request` with url.
+    pub fn post(url: impl Into<String>) -> RequestBuilder {
+        RequestBuilder::new(url).method(Method::POST)
+    }
+
+    /// Creates a new [`PUT`][Method::PUT] `Request` with url.
+    pub fn put(url: impl Into<String>) -> RequestBuilder {
+        RequestBuilder::new(url).method(Method::PUT)
+    }
+
+    /// Creates a new [`DELETE`][Method::DELETE] `Request` with url.
+    pub fn delete(url: impl Into<String>) -> RequestBuilder {
+        RequestBuilder::new(url).method(Method::DELETE)
+    }
+
+    /// Creates a new [`PATCH`][Method::PATCH] `Request` with url.
+    pub fn patch(url: impl Into<String>) -> RequestBuilder {
+        RequestBuilder::new(url).method(Method::PATCH)
+    }
+
+    /// The URL of the request.
+    pub fn url(&self) -> String {
+        self.raw.url().to_string()
+    }
+
+    /// Gets a copy of the headers.
+    ///
+    /// Unlike in the browser, changing the returned headers does not change the request.
+    pub fn headers(&self) -> Headers {
+        Headers::from_raw(self.raw.headers().clone())
+    }
+
+    /// Has the request body been consumed?
+    ///
+    /// The body of a native request can be read any number of times, so this is always false.
+    pub fn body_used(&self) -> bool {
+        false
+    }
+
+    /// Reads the request body as a String.
+    pub async fn text(&self) -> Result<String, Error> {
+        Ok(String::from_utf8_lossy(&self.binary().await?).into_owned())
+    }
+
+    /// Reads the request body, parsing it as JSON.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub async fn json<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        serde_json::from_str::<T>(&self.text().await?).map_err(|error| Error::Decode {
+            url: self.url(),
+            source: Box::new(error),
+        })
+    }
+
+    /// Gets the binary request body.
+    ///
+    /// Fails if the body is a stream.
+    pub async fn binary(&self) -> Result<Vec<u8>, Error> {
+        match self.raw.body() {
+            None => Ok(Vec::new()),
+            Some(body) => body.as_bytes().map(<[u8]>::to_vec).ok_or_else(stream_body),
+        }
+    }
+
+    /// Return the parsed method for the request
+    pub fn method(&self) -> Method {
+        self.raw.method().clone()
+    }
+
+    /// Creates a copy of this request, which can be sent independently.
+    ///
+    /// Fails if the body is a stream.
+    pub fn try_clone(&self) -> Result<Request, Error> {
+        let raw = self.raw.try_clone().ok_or_else(stream_body)?;
+        Ok(Request {
+            raw,
+            client: self.client.clone(),
+        })
+    }
+
+    /// Reads the request to completion, converting it into an [`http::Request`].
+    pub async fn into_http(self) -> Result<http::Request<Vec<u8>>, Error> {
+        let body = self.binary().await?;
+        let mut request = http::Request::new(body);
+        *request.method_mut() = self.method();
+        *request.uri_mut() = http::Uri::try_from(self.url())
+            .map_err(|e| Error::InvalidUrl(format!("{:?}: {}", self.url(), e)))?;
+        *request.headers_mut() = self.raw.headers().clone();
+        Ok(request)
+    }
+
+    /// Executes the request.
+    ///
+    /// With the `tracing` feature, the request is recorded in a `http.request` span and sent with
+    /// a W3C `traceparent` header.
+    pub async fn send(self) -> Result<Response, Error> {
+        #[cfg(feature = "tracing")]
+        let (request, span) = self.start_span();
+        #[cfg(not(feature = "tracing"))]
+        let request = self;
+        let sending = request.send_untraced();
+        #[cfg(feature = "tracing")]
+        let sending = span.instrument(sending);
+        let result = sending.await;
+        #[cfg(feature = "tracing")]
+        span.finish(&result);
+        result
+    }
+
+    /// Starts the span of the request, which may add a `traceparent` header.
+    #[cfg(feature = "tracing")]
+    fn start_span(mut self) -> (Self, trace::RequestSpan) {
+        let headers = self.headers();
+        let span = trace::RequestSpan::start(&self.method(), &self.url(), &headers);
+        *self.raw.headers_mut() = headers.into_raw();
+        (self, span)
+    }
+
+    async fn send_untraced(self) -> Result<Response, Error> {
+        let timeout = self.raw.timeout().copied();
+        let method = self.method();
+        let url = self.url();
+        let client = self.client.as_ref().unwrap_or(&CLIENT);
+        match client.execute(self.raw).await {
+            Ok(response) => Ok(Response::from(response).with_method(method)),
+            Err(e) if e.is_timeout() => Err(Error::Timeout(timeout.unwrap_or_default())),
+            Err(e) => Err(reqwest_to_error(method, url, e)),
+        }
+    }
+}
+
+impl From<reqwest::Request> for Request {
+    fn from(raw: reqwest::Request) -> Self {
+        Request { raw, client: None }
+    }
+}
+
+/// Builds a request with the method, URI, headers and body of an [`http::Request`].
+///
+/// Unlike in the browser, the URI has to be absolute.
+impl<B: AsRef<[u8]>> TryFrom<http::Request<B>> for Request {
+    type Error = crate::error::Error;
+
+    fn try_from(request: http::Request<B>) -> Result<Self, Self::Error> {
+        let (parts, body) = request.into_parts();
+        let builder = RequestBuilder::new(parts.uri.to_string())
+            .method(parts.method)
+            .headers(Headers::try_from(&parts.headers)?);
+        match body.as_ref() {
+            [] => builder.build(),
+            body => builder.body(body.to_vec()),
+        }
+    }
+}
+
+impl From<Request> for reqwest::Request {
+    fn from(val: Request) -> Self {
+        val.raw
+    }
+}
+
+fn stream_body() -> Error {
+    Error::GlooError("request body is a stream".to_string())
+}
+
+impl fmt::Debug for Request {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Request")
+            .field("url", &self.url())
+            .field("headers", &self.headers())
+            .field("body_used", &self.body_used())
+            .finish()
+    }
+}
@@ -0,0 +1,456 @@
+use super::{reqwest_to_error, Headers};
+use crate::Error;
+use futures_channel::oneshot;
+use http::{HeaderMap, Method, StatusCode};
+use std::convert::TryFrom;
+use std::error::Error as StdError;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "serde")]
+use crate::http::codec::{self, BodyCodec};
+#[cfg(feature = "compression")]
+use crate::http::ContentEncoding;
+#[cfg(feature = "serde")]
+use serde::de::DeserializeOwned;
+
+/// The [`Request`](super::Request)'s response
+pub struct Response {
+    status: StatusCode,
+    status_text: Option<String>,
+    headers: HeaderMap,
+    url: String,
+    method: Method,
+    body: Mutex<Option<Arc<Body>>>,
+}
+
+impl Response {
+    /// Returns an instance of response builder
+    pub fn builder() -> ResponseBuilder {
+        ResponseBuilder::new()
+    }
+
+    /// The URL of the response.
+    ///
+    /// The returned value will be the final URL obtained after any redirects. It is empty for
+    /// responses created with a [`ResponseBuilder`].
+    pub fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    /// the [HTTP status code](https://developer.mozilla.org/en-US/docs/Web/HTTP/Status) of the
+    /// response.
+    pub fn status(&self) -> u16 {
+        self.status.as_u16()
+    }
+
+    /// Whether the [HTTP status code](https://developer.mozilla.org/en-US/docs/Web/HTTP/Status)
+    /// was a success code (in the range `200 - 299`).
+    pub fn ok(&self) -> bool {
+        self.status.is_success()
+    }
+
+    /// Turns a response with an unsuccessful status code into an [`Error::Status`].
+    ///
+    /// Responses with a status code in the range `200 - 299` are returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use gloo_net::http::native::Request;
+    /// # async fn no_run() -> Result<(), gloo_net::Error> {
+    /// let body = Request::get("/path")
+    ///     .send()
+    ///     .await?
+    ///     .error_for_status()?
+    ///     .text()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn error_for_status(self) -> Result<Self, Error> {
+        if self.ok() {
+            Ok(self)
+        } else {
+            Err(Error::Status {
+                url: self.url(),
+                status: self.status(),
+                status_text: self.status_text(),
+            })
+        }
+    }
+
+    /// The status message corresponding to the
+    /// [HTTP status code](https://developer.mozilla.org/en-US/docs/Web/HTTP/Status) from
+    /// `Response::status`.
+    ///
+    /// Unless it was set with [`ResponseBuilder::status_text`], this is the canonical reason
+    /// phrase of the status, e.g. 'OK' for a status code 200 or 'Not Found' for 404, regardless of
+    /// what the server sent.
+    pub fn status_text(&self) -> String {
+        match &self.status_text {
+            Some(status_text) => status_text.clone(),
+            None => self.status.canonical_reason().unwrap_or("").to_string(),
+        }
+    }
+
+    /// Gets the headers.
+    pub fn headers(&self) -> Headers {
+        Headers::from_raw(self.headers.clone())
+    }
+
+    /// Has the response body been consumed?
+    ///
+    /// If true, then any future attempts to consume the body will error.
+    pub fn body_used(&self) -> bool {
+        self.body.lock().unwrap().is_none()
+    }
+
+    /// Creates a copy of this response, whose body can be read independently.
+    ///
+    /// Fails if the body has already been used.
+    pub fn try_clone(&self) -> Result<Response, Error> {
+        let body = self
+            .body
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| Error::BodyUsed { url: self.url() })?;
+        Ok(Response {
+            status: self.status,
+            status_text: self.status_text.clone(),
+            headers: self.headers.clone(),
+            url: self.url.clone(),
+            method: self.method.clone(),
+            body: Mutex::new(Some(body)),
+        })
+    }
+
+    /// The value of the `Content-Length` header, if present and valid.
+    pub fn content_length(&self) -> Option<u64> {
+        self.headers().get("Content-Length")?.trim().parse().ok()
+    }
+
+    /// Reads the response to completion, converting it into an [`http::Response`].
+    pub async fn into_http(self) -> Result<http::Response<Vec<u8>>, Error> {
+        let body = self.binary().await?;
+        let mut response = http::Response::new(body);
+        *response.status_mut() = self.status;
+        *response.headers_mut() = self.headers;
+        Ok(response)
+    }
+
+    /// Decompresses a body which was not decoded by the HTTP client, returning a response with the
+    /// decompressed body.
+    ///
+    /// This is meant for bodies that are compressed without a `Content-Encoding` header, such as
+    /// `.gz` files served as `application/gzip`. The body is read to completion. The
+    /// `Content-Encoding` and `Content-Length` headers are dropped.
+    #[cfg(feature = "compression")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "compression")))]
+    pub async fn decompress(self, encoding: ContentEncoding) -> Result<Response, Error> {
+        let body = self.binary().await?;
+        let body = encoding.decode(&body).map_err(|e| Error::Decode {
+            url: self.url(),
+            source: Box::new(e),
+        })?;
+        let mut headers = self.headers;
+        headers.remove(http::header::CONTENT_ENCODING);
+        headers.remove(http::header::CONTENT_LENGTH);
+        Ok(Response {
+            headers,
+            body: Mutex::new(Some(Arc::new(Body::read(body)))),
+            ..self
+        })
+    }
+
+    /// Reads the response to completion, decoding it with `codec`.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub async fn decode<C, T>(&self, codec: C) -> Result<T, Error>
+    where
+        C: BodyCodec,
+        T: DeserializeOwned,
+    {
+        let body = self.binary().await?;
+        codec.decode(&body).map_err(|source| Error::Decode {
+            url: self.url(),
+            source,
+        })
+    }
+
+    /// Reads the response to completion, decoding it with the enabled [`BodyCodec`] that matches
+    /// its `Content-Type` header.
+    ///
+    /// Fails with [`Error::Decode`] if no enabled codec matches.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub async fn decode_negotiated<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        let content_type = self.headers().get("Content-Type").unwrap_or_default();
+        let body = self.binary().await?;
+        let result = codec::decode_negotiated(&content_type, &body)
+            .unwrap_or_else(|| Err(format!("unsupported content type {:?}", content_type).into()));
+        result.map_err(|source| Error::Decode {
+            url: self.url(),
+            source,
+        })
+    }
+
+    /// Reads the response to completion, parsing it as JSON.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub async fn json<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        serde_json::from_str::<T>(&self.text().await?).map_err(|error| Error::Decode {
+            url: self.url(),
+            source: Box::new(error),
+        })
+    }
+
+    /// Reads the response as a String.
+    pub async fn text(&self) -> Result<String, Error> {
+        Ok(String::from_utf8_lossy(&self.binary().await?).into_owned())
+    }
+
+    /// Gets the binary response
+    pub async fn binary(&self) -> Result<Vec<u8>, Error> {
+        let body = self
+            .body
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or_else(|| Error::BodyUsed { url: self.url() })?;
+        body.bytes()
+            .await
+            .map_err(|error| reqwest_to_error(self.method.clone(), self.url(), error))
+    }
+
+    /// Sets the method of the request, which errors reading the body are reported with.
+    pub(crate) fn with_method(mut self, method: Method) -> Self {
+        self.method = method;
+        self
+    }
+}
+
+impl From<reqwest::Response> for Response {
+    fn from(raw: reqwest::Response) -> Self {
+        Self {
+            status: raw.status(),
+            status_text: None,
+            headers: raw.headers().clone(),
+            url: raw.url().to_string(),
+            method: Method::GET,
+            body: Mutex::new(Some(Arc::new(Body::unread(raw)))),
+        }
+    }
+}
+
+/// The body of a response, which is shared with the copies of the response.
+///
+/// The first copy to read it reads it from the network, the others wait for it.
+struct Body {
+    state: Mutex<BodyState>,
+}
+
+type BodyResult = Result<Vec<u8>, Arc<dyn StdError + Send + Sync>>;
+
+struct BodyState {
+    unread: Option<reqwest::Response>,
+    read: Option<BodyResult>,
+    waiting: Vec<oneshot::Sender<()>>,
+}
+
+impl Body {
+    fn unread(response: reqwest::Response) -> Self {
+        Self::new(Some(response), None)
+    }
+
+    #[cfg(feature = "compression")]
+    fn read(bytes: Vec<u8>) -> Self {
+        Self::new(None, Some(Ok(bytes)))
+    }
+
+    fn new(unread: Option<reqwest::Response>, read: Option<BodyResult>) -> Self {
+        Self {
+            state: Mutex::new(BodyState {
+                unread,
+                read,
+                waiting: Vec::new(),
+            }),
+        }
+    }
+
+    async fn bytes(&self) -> BodyResult {
+        loop {
+            let unread = {
+                let mut state = self.state.lock().unwrap();
+                if let Some(read) = &state.read {
+                    return read.clone();
+                }
+                state.unread.take().ok_or_else(|| {
+                    let (sender, receiver) = oneshot::channel();
+                    state.waiting.push(sender);
+                    receiver
+                })
+            };
+            match unread {
+                Ok(response) => return self.read_from(response).await,
+                Err(waiting) => {
+                    let _ = waiting.await;
+                }
+            }
+        }
+    }
+
+    async fn read_from(&self, response: reqwest::Response) -> BodyResult {
+        // Wakes up the copies waiting for the body even if this future is dropped early.
+        let mut reading = Reading {
+            body: self,
+            read: Some(Err(Arc::from(Box::<dyn StdError + Send + Sync>::from(
+                "the body was dropped while it was being read",
+            )))),
+        };
+        let read: BodyResult = match response.bytes().await {
+            Ok(bytes) => Ok(bytes.to_vec()),
+            Err(error) => Err(Arc::new(error)),
+        };
+        reading.read = Some(read.clone());
+        read
+    }
+}
+
+/// Stores the result of reading a [`Body`] and wakes up the copies waiting for it when dropped.
+struct Reading<'a> {
+    body: &'a Body,
+    read: Option<BodyResult>,
+}
+
+impl Drop for Reading<'_> {
+    fn drop(&mut self) {
+        let mut state = self.body.state.lock().unwrap();
+        state.read = self.read.take();
+        for waiting in state.waiting.drain(..) {
+            let _ = waiting.send(());
+        }
+    }
+}
+
+/// Builds a response with the status, headers and body of an [`http::Response`].
+impl<B: AsRef<[u8]>> TryFrom<http::Response<B>> for Response {
+    type Error = Error;
+
+    fn try_from(response: http::Response<B>) -> Result<Self, Self::Error> {
+        let (parts, body) = response.into_parts();
+        let mut response = http::Response::new(reqwest::Body::from(body.as_ref().to_vec()));
+        *response.status_mut() = parts.status;
+        *response.headers_mut() = parts.headers;
+
+        let mut response = Response::from(reqwest::Response::from(response));
+        response.url = String::new();
+        response.status_text = parts
+            .status
+            .canonical_reason()
+            .map(|status_text| status_text.to_string());
+        Ok(response)
+    }
+}
+
+impl fmt::Debug for Response {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Response")
+            .field("url", &self.url())
+            .field("status", &self.status())
+            .field("headers", &self.headers())
+            .field("body_used", &self.body_used())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A builder for a [`Response`], e.g. to answer requests in tests.
+pub struct ResponseBuilder {
+    headers: Headers,
+    status: StatusCode,
+    status_text: Option<String>,
+}
+
+impl ResponseBuilder {
+    /// Creates a new response object which defaults to status 200
+    /// for other status codes, call Self.status(400)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace _all_ the headers.
+    pub fn headers(mut self, headers: Headers) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// Sets a header.
+    pub fn header(self, key: &str, value: &str) -> Self {
+        self.headers.set(key, value);
+        self
+    }
+
+    /// Set the status code
+    ///
+    /// Invalid status codes, i.e. outside of `100 - 999`, are ignored.
+    pub fn status(mut self, status: u16) -> Self {
+        if let Ok(status) = StatusCode::from_u16(status) {
+            self.status = status;
+        }
+        self
+    }
+
+    /// Set the status text
+    pub fn status_text(mut self, status_text: &str) -> Self {
+        self.status_text = Some(status_text.to_string());
+        self
+    }
+
+    /// A convenience method to set JSON as response body
+    ///
+    /// # Note
+    ///
+    /// This method also sets the `Content-Type` header to `application/json`
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn json<T: serde::Serialize + ?Sized>(self, value: &T) -> Result<Response, Error> {
+        let json = serde_json::to_string(value)?;
+        self.header("Content-Type", "application/json")
+            .body(Some(json))
+    }
+
+    /// Set the response body and return the response
+    pub fn body<T>(self, data: Option<T>) -> Result<Response, Error>
+    where
+        T: Into<reqwest::Body>,
+    {
+        let body = data.map(Into::into).unwrap_or_else(|| Vec::new().into());
+        let mut raw = http::Response::new(body);
+        *raw.status_mut() = self.status;
+        *raw.headers_mut() = self.headers.into_raw();
+
+        let mut response = Response::from(reqwest::Response::from(raw));
+        response.url = String::new();
+        response.status_text = self.status_text;
+        Ok(response)
+    }
+}
+
+impl Default for ResponseBuilder {
+    fn default() -> Self {
+        Self {
+            headers: Headers::new(),
+            status: StatusCode::OK,
+            status_text: None,
+        }
+    }
+}
+
+impl fmt::Debug for ResponseBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseBuilder")
+            .field("headers", &self.headers)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,86 @@
+use crate::http::BodyStream;
+use crate::Error;
+use futures_core::Stream;
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A snapshot of how much of a body has been transferred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// The number of bytes transferred so far.
+    pub loaded: u64,
+    /// The total size of the body, if known.
+    ///
+    /// For downloads this is taken from the `Content-Length` header. Note that it is the size of
+    /// the body *on the wire*: when the server compresses the response, more bytes than this are
+    /// usually received.
+    pub total: Option<u64>,
+}
+
+impl Progress {
+    /// The transferred fraction of the body, between `0.0` and `1.0`.
+    ///
+    /// Returns `None` when the total size is unknown.
+    pub fn fraction(&self) -> Option<f64> {
+        self.total.map(|total| match total {
+            0 => 1.0,
+            total => (self.loaded as f64 / total as f64).min(1.0),
+        })
+    }
+}
+
+/// A [`BodyStream`] which reports its [`Progress`] as chunks arrive.
+///
+/// Created by [`Response::bytes_stream_with_progress`](super::Response::bytes_stream_with_progress).
+/// The callback is invoked after every chunk, before the chunk is yielded.
+pub struct ProgressStream {
+    inner: BodyStream,
+    progress: Progress,
+    on_progress: Box<dyn FnMut(Progress)>,
+}
+
+impl ProgressStream {
+    pub(crate) fn new(
+        inner: BodyStream,
+        total: Option<u64>,
+        on_progress: impl FnMut(Progress) + 'static,
+    ) -> Self {
+        Self {
+            inner,
+            progress: Progress { loaded: 0, total },
+            on_progress: Box::new(on_progress),
+        }
+    }
+
+    /// The progress made so far.
+    pub fn progress(&self) -> Progress {
+        self.progress
+    }
+}
+
+impl Stream for ProgressStream {
+    type Item = Result<Vec<u8>, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let item = match Pin::new(&mut self.inner).poll_next(cx) {
+            Poll::Ready(item) => item,
+            Poll::Pending => return Poll::Pending,
+        };
+        if let Some(Ok(chunk)) = &item {
+            self.progress.loaded += chunk.len() as u64;
+            let progress = self.progress;
+            (self.on_progress)(progress);
+        }
+        Poll::Ready(item)
+    }
+}
+
+impl fmt::Debug for ProgressStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgressStream")
+            .field("inner", &self.inner)
+            .field("progress", &self.progress)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,582 @@
+use crate::Error;
+use serde::ser::{self, Impossible, Serialize};
+use std::fmt;
+
+/// Flattens `params` into `(name, value)` pairs of a query string.
+///
+/// `params` has to serialize as a struct or a map. Nested structs and maps are written with
+/// brackets, `filter[status]=open`, and sequences repeat their name, `tag=a&tag=b`. Structs and
+/// sequences inside of sequences are numbered, `items[0][id]=1`. `None` and unit values are
+/// left out, and enum variants without data are written as their name.
+pub(crate) fn serialize_query<T: Serialize + ?Sized>(
+    params: &T,
+) -> Result<Vec<(String, String)>, Error> {
+    let mut pairs = Vec::new();
+    params
+        .serialize(ValueSerializer {
+            pairs: &mut pairs,
+            name: None,
+            prefix: None,
+        })
+        .map_err(|e| Error::EncodeQuery(Box::new(e)))?;
+    Ok(pairs)
+}
+
+#[derive(Debug)]
+struct QueryError(String);
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+impl ser::Error for QueryError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+fn top_level() -> QueryError {
+    QueryError("query parameters have to be a struct or a map".to_string())
+}
+
+/// The name of the field `field` of a struct or map named `prefix`.
+fn child(prefix: Option<&str>, field: &str) -> String {
+    match prefix {
+        Some(prefix) => format!("{prefix}[{field}]"),
+        None => field.to_string(),
+    }
+}
+
+/// Serializes one value into `pairs`.
+struct ValueSerializer<'a> {
+    pairs: &'a mut Vec<(String, String)>,
+    /// The name of the value if it is a scalar, `None` at the top level.
+    name: Option<String>,
+    /// The name which the fields of the value are nested in, if it is a struct, map or sequence.
+    prefix: Option<String>,
+}
+
+impl<'a> ValueSerializer<'a> {
+    fn named(pairs: &'a mut Vec<(String, String)>, name: String) -> Self {
+        Self {
+            pairs,
+            name: Some(name.clone()),
+            prefix: Some(name),
+        }
+    }
+
+    fn push(self, value: impl fmt::Display) -> Result<(), QueryError> {
+        let name = self.name.ok_or_else(top_level)?;
+        self.pairs.push((name, value.to_string()));
+        Ok(())
+    }
+
+    fn seq(self) -> Result<SeqSerializer<'a>, QueryError> {
+        Ok(SeqSerializer {
+            pairs: self.pairs,
+            name: self.prefix.ok_or_else(top_level)?,
+            index: 0,
+        })
+    }
+
+    fn map(self) -> MapSerializer<'a> {
+        MapSerializer {
+            pairs: self.pairs,
+            prefix: self.prefix,
+            key: None,
+        }
+    }
+
+    /// The serializer for the data of the enum variant `variant`.
+    fn variant(self, variant: &str) -> Self {
+        let name = child(self.prefix.as_deref(), variant);
+        Self::named(self.pairs, name)
+    }
+}
+
+impl<'a> ser::Serializer for ValueSerializer<'a> {
+    type Ok = ();
+    type Error = QueryError;
+    type SerializeSeq = SeqSerializer<'a>;
+    type SerializeTuple = SeqSerializer<'a>;
+    type SerializeTupleStruct = SeqSerializer<'a>;
+    type SerializeTupleVariant = SeqSerializer<'a>;
+    type SerializeMap = MapSerializer<'a>;
+    type SerializeStruct = MapSerializer<'a>;
+    type SerializeStructVariant = MapSerializer<'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), QueryError> {
+        self.push(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), QueryError> {
+        self.push(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), QueryError> {
+        self.push(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), QueryError> {
+        self.push(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), QueryError> {
+        self.push(v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), QueryError> {
+        self.push(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), QueryError> {
+        self.push(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), QueryError> {
+        self.push(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), QueryError> {
+        self.push(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), QueryError> {
+        self.push(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), QueryError> {
+        self.push(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), QueryError> {
+        self.push(v)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), QueryError> {
+        self.push(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), QueryError> {
+        self.push(v)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), QueryError> {
+        self.push(v)
+    }
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<(), QueryError> {
+        Err(QueryError(
+            "bytes cannot be written to a query string".to_string(),
+        ))
+    }
+
+    fn serialize_none(self) -> Result<(), QueryError> {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), QueryError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), QueryError> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<(), QueryError> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<(), QueryError> {
+        self.push(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), QueryError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), QueryError> {
+        value.serialize(self.variant(variant))
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<SeqSerializer<'a>, QueryError> {
+        self.seq()
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<SeqSerializer<'a>, QueryError> {
+        self.seq()
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<SeqSerializer<'a>, QueryError> {
+        self.seq()
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<SeqSerializer<'a>, QueryError> {
+        self.variant(variant).seq()
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<MapSerializer<'a>, QueryError> {
+        Ok(self.map())
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<MapSerializer<'a>, QueryError> {
+        Ok(self.map())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<MapSerializer<'a>, QueryError> {
+        Ok(self.variant(variant).map())
+    }
+}
+
+/// Serializes the elements of a sequence. Scalars repeat the name of the sequence, while other
+/// elements are nested under their index.
+struct SeqSerializer<'a> {
+    pairs: &'a mut Vec<(String, String)>,
+    name: String,
+    index: usize,
+}
+
+impl SeqSerializer<'_> {
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), QueryError> {
+        let prefix = format!("{}[{}]", self.name, self.index);
+        self.index += 1;
+        value.serialize(ValueSerializer {
+            pairs: self.pairs,
+            name: Some(self.name.clone()),
+            prefix: Some(prefix),
+        })
+    }
+}
+
+impl ser::SerializeSeq for SeqSerializer<'_> {
+    type Ok = ();
+    type Error = QueryError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), QueryError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), QueryError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer<'_> {
+    type Ok = ();
+    type Error = QueryError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), QueryError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), QueryError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer<'_> {
+    type Ok = ();
+    type Error = QueryError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), QueryError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), QueryError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleVariant for SeqSerializer<'_> {
+    type Ok = ();
+    type Error = QueryError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), QueryError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), QueryError> {
+        Ok(())
+    }
+}
+
+/// Serializes the entries of a struct or map, nested under `prefix` unless at the top level.
+struct MapSerializer<'a> {
+    pairs: &'a mut Vec<(String, String)>,
+    prefix: Option<String>,
+    key: Option<String>,
+}
+
+impl MapSerializer<'_> {
+    fn field<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), QueryError> {
+        let name = child(self.prefix.as_deref(), key);
+        value.serialize(ValueSerializer::named(self.pairs, name))
+    }
+}
+
+impl ser::SerializeMap for MapSerializer<'_> {
+    type Ok = ();
+    type Error = QueryError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), QueryError> {
+        self.key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), QueryError> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| QueryError("map value without a key".to_string()))?;
+        self.field(&key, value)
+    }
+
+    fn end(self) -> Result<(), QueryError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for MapSerializer<'_> {
+    type Ok = ();
+    type Error = QueryError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), QueryError> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), QueryError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStructVariant for MapSerializer<'_> {
+    type Ok = ();
+    type Error = QueryError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), QueryError> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), QueryError> {
+        Ok(())
+    }
+}
+
+/// Serializes a map key, which has to be a string, a number, a character or a unit variant.
+struct KeySerializer;
+
+impl KeySerializer {
+    fn invalid() -> QueryError {
+        QueryError("map keys have to be strings or numbers".to_string())
+    }
+}
+
+impl ser::Serializer for KeySerializer {
+    type Ok = String;
+    type Error = QueryError;
+    type SerializeSeq = Impossible<String, QueryError>;
+    type SerializeTuple = Impossible<String, QueryError>;
+    type SerializeTupleStruct = Impossible<String, QueryError>;
+    type SerializeTupleVariant = Impossible<String, QueryError>;
+    type SerializeMap = Impossible<String, QueryError>;
+    type SerializeStruct = Impossible<String, QueryError>;
+    type SerializeStructVariant = Impossible<String, QueryError>;
+
+    fn serialize_bool(self, v: bool) -> Result<String, QueryError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<String, QueryError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<String, QueryError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<String, QueryError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<String, QueryError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<String, QueryError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<String, QueryError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<String, QueryError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<String, QueryError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<String, QueryError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<String, QueryError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_f32(self, _: f32) -> Result<String, QueryError> {
+        Err(Self::invalid())
+    }
+
+    fn serialize_f64(self, _: f64) -> Result<String, QueryError> {
+        Err(Self::invalid())
+    }
+
+    fn serialize_char(self, v: char) -> Result<String, QueryError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<String, QueryError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<String, QueryError> {
+        Err(Self::invalid())
+    }
+
+    fn serialize_none(self) -> Result<String, QueryError> {
+        Err(Self::invalid())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<String, QueryError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<String, QueryError> {
+        Err(Self::invalid())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<String, QueryError> {
+        Err(Self::invalid())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<String, QueryError> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<String, QueryError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<String, QueryError> {
+        Err(Self::invalid())
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, QueryError> {
+        Err(Self::invalid())
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, QueryError> {
+        Err(Self::invalid())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, QueryError> {
+        Err(Self::invalid())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, QueryError> {
+        Err(Self::invalid())
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, QueryError> {
+        Err(Self::invalid())
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, QueryError> {
+        Err(Self::invalid())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, QueryError> {
+        Err(Self::invalid())
+    }
+}
@@ -1,15 +1,34 @@
-use crate::http::{Headers, QueryParams, Response};
-use crate::{js_to_error, Error};
-use http::Method;
-use js_sys::{ArrayBuffer, Reflect, Uint8Array};
+use crate::http::stream::readable_stream_from;
+#[cfg(feature = "tracing")]
+use crate::http::trace;
+#[cfg(feature = "compression")]
+use crate::http::{compression, ContentEncoding};
+#[cfg(feature = "serde")]
+use crate::http::{query_serializer, BodyCodec};
+use crate::http::{
+    FetchTransport, Headers, Interceptor, Multipart, Next, QueryParams, Response, RetryPolicy,
+    Transport, XhrRequest,
+};
+use crate::{js_to_error, js_to_js_error, Error};
+use futures_core::Stream;
+use gloo_timers::future::TimeoutFuture;
+use http::{HeaderMap, Method};
+use js_sys::{ArrayBuffer, Function, Promise, Reflect, Uint8Array};
 use std::convert::{From, TryFrom, TryInto};
 use std::fmt;
+use std::future::{poll_fn, Future};
+use std::mem;
+use std::pin::Pin;
+use std::rc::Rc;
 use std::str::FromStr;
+use std::task::Poll;
+use std::time::Duration;
+use wasm_bindgen::closure::Closure;
 use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::JsFuture;
 use web_sys::{
-    AbortSignal, FormData, ObserverCallback, ReadableStream, ReferrerPolicy, RequestCache,
-    RequestCredentials, RequestMode, RequestRedirect,
+    AbortController, AbortSignal, FormData, ObserverCallback, ReadableStream, ReferrerPolicy,
+    RequestCache, RequestCredentials, RequestMode, RequestRedirect,
 };
 
 #[cfg(feature = "json")]
@@ -22,26 +41,78 @@ pub struct RequestBuilder {
     headers: Headers,
     query: QueryParams,
     url: String,
+    timeout: Option<Duration>,
+    retry: Option<RetryPolicy>,
+    interceptors: Vec<Rc<dyn Interceptor>>,
+    transport: Option<Rc<dyn Transport>>,
+    #[cfg(feature = "compression")]
+    compression: Option<ContentEncoding>,
 }
 
 impl RequestBuilder {
     /// Creates a new request that will be sent to `url`.
     ///
-    /// Uses `GET` by default. `url` can be a `String`, a `&str`, or a `Cow<'a, str>`.
-    pub fn new(url: &str) -> Self {
+    /// Uses `GET` by default. `url` can be a `String`, a `&str`, a `Cow<'a, str>`, or a
+    /// [`Url`](crate::http::Url).
+    pub fn new(url: impl Into<String>) -> Self {
         Self {
             options: web_sys::RequestInit::new(),
             headers: Headers::new(),
             query: QueryParams::new(),
             url: url.into(),
+            timeout: None,
+            retry: None,
+            interceptors: Vec::new(),
+            transport: None,
+            #[cfg(feature = "compression")]
+            compression: None,
         }
     }
 
     /// Set the body for this request.
     pub fn body(mut self, body: impl Into<JsValue>) -> Result<Request, Error> {
-        self.options.body(Some(&body.into()));
+        let body = body.into();
+        self.options.body(Some(&body));
 
-        self.try_into()
+        let mut request: Request = self.try_into()?;
+        request.body = Some(body);
+        Ok(request)
+    }
+
+    /// Set a body which is read from `stream` while the request is being sent, rather than
+    /// buffered up front.
+    ///
+    /// The request is sent with `duplex: "half"`, which streaming request bodies require. Note
+    /// that browsers only support streaming uploads over HTTP/2 or newer, and not every browser
+    /// supports them at all; the request fails if they are not supported.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use gloo_net::http::Request;
+    /// # use futures::StreamExt;
+    /// # async fn no_run() -> Result<(), gloo_net::Error> {
+    /// let rows = futures::stream::iter(0..1000).map(|i| format!("{}\n", i).into_bytes());
+    /// let resp = Request::post("/export").body_stream(rows)?.send().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn body_stream<S>(self, stream: S) -> Result<Request, Error>
+    where
+        S: Stream<Item = Vec<u8>> + 'static,
+    {
+        let body = readable_stream_from(stream)?;
+        Reflect::set(
+            &self.options,
+            &JsValue::from_str("duplex"),
+            &JsValue::from_str("half"),
+        )
+        .map_err(js_to_error)?;
+        let mut request = self.body(body)?;
+        // `XMLHttpRequest` cannot send a `ReadableStream`, so it reads the stream from the
+        // underlying request instead.
+        request.body = None;
+        Ok(request)
     }
 
     /// A string indicating how the request will interact with the browser’s HTTP cache.
@@ -107,6 +178,40 @@ impl RequestBuilder {
         self
     }
 
+    /// Append the fields of `params` to the query parameters.
+    ///
+    /// `params` has to serialize as a struct or a map. Nested structs and maps are written with
+    /// brackets, `filter[status]=open`, and sequences repeat their name, `tag=a&tag=b`. Structs
+    /// inside of sequences are numbered, `items[0][id]=1`. Fields which are `None` are left out.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use gloo_net::http::Request;
+    /// # use serde::Serialize;
+    /// # fn no_run() -> Result<(), gloo_net::Error> {
+    /// #[derive(Serialize)]
+    /// struct Search<'a> {
+    ///     q: &'a str,
+    ///     tags: Vec<&'a str>,
+    ///     page: Option<u32>,
+    /// }
+    ///
+    /// let search = Search { q: "gloo", tags: vec!["net", "http"], page: None };
+    /// let request = Request::get("/search").serialize_query(&search)?;
+    /// // Result URL: /search?q=gloo&tags=net&tags=http
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn serialize_query<T: serde::Serialize + ?Sized>(self, params: &T) -> Result<Self, Error> {
+        for (name, value) in query_serializer::serialize_query(params)? {
+            self.query.append(&name, &value);
+        }
+        Ok(self)
+    }
+
     /// The subresource integrity value of the request (e.g.,
     /// `sha256-BpfBw7ivV8q2jLiT13fxDYAe2tJllusRSZ273h2nFSE=`).
     pub fn integrity(mut self, integrity: &str) -> Self {
@@ -126,6 +231,44 @@ impl RequestBuilder {
         self.header("Content-Type", "application/json").body(json)
     }
 
+    /// Serializes `value` with `codec` and sets it as request body.
+    ///
+    /// # Note
+    ///
+    /// This method also sets the `Content-Type` header to the MIME type of `codec`.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn encode<C, T>(self, codec: C, value: &T) -> Result<Request, Error>
+    where
+        C: BodyCodec,
+        T: serde::Serialize + ?Sized,
+    {
+        let body = codec.encode(value).map_err(Error::Encode)?;
+        self.header("Content-Type", codec.content_type())
+            .body(Uint8Array::from(body.as_slice()))
+    }
+
+    /// Adds the MIME type of `codec` to the `Accept` header, to ask the server for a response
+    /// body in that format.
+    ///
+    /// Call this once per supported format, in order of preference, and read the response with
+    /// [`Response::decode_negotiated`].
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn accept<C: BodyCodec>(self, codec: C) -> Self {
+        self.headers.append("Accept", codec.content_type());
+        self
+    }
+
+    /// A convenience method to set a [`Multipart`] form as request body
+    ///
+    /// # Note
+    ///
+    /// The `Content-Type` header, including the multipart boundary, is set by the browser.
+    pub fn multipart(self, multipart: Multipart) -> Result<Request, Error> {
+        self.body(multipart)
+    }
+
     /// The request method, e.g., GET, POST.
     pub fn method(mut self, method: Method) -> Self {
         self.options.method(method.as_ref());
@@ -177,6 +320,71 @@ impl RequestBuilder {
         self.options.signal(signal);
         self
     }
+
+    /// Aborts the request if no response has been received after `timeout`.
+    ///
+    /// When the timeout elapses the underlying `fetch` is aborted and [`Request::send`] returns
+    /// [`Error::Timeout`]. The timeout covers the wait for the response headers; reading the body
+    /// afterwards is not limited by it.
+    ///
+    /// This can be combined with [`abort_signal`](Self::abort_signal): aborting that signal still
+    /// aborts the request.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Retries the request according to `policy` when it fails.
+    ///
+    /// Every attempt sends a fresh copy of the request, so the body is sent again in full. When a
+    /// [`timeout`](Self::timeout) is set as well, it applies to each attempt separately.
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Compresses the body with `encoding` and sets the `Content-Encoding` header accordingly.
+    ///
+    /// The body is compressed when the request is sent, with `CompressionStream` where the
+    /// browser supports it and in Rust otherwise. It is buffered in memory to do so, which also
+    /// applies to bodies set with [`body_stream`](Self::body_stream). Only use this with servers
+    /// that accept compressed request bodies.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use gloo_net::http::{ContentEncoding, Request};
+    /// # async fn no_run() -> Result<(), gloo_net::Error> {
+    /// # let events = vec![0u8; 1024];
+    /// let resp = Request::post("/events")
+    ///     .compress(ContentEncoding::Gzip)
+    ///     .json(&events)?
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "compression")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "compression")))]
+    pub fn compress(mut self, encoding: ContentEncoding) -> Self {
+        self.compression = Some(encoding);
+        self
+    }
+
+    /// Sends the request with `transport` instead of `fetch`.
+    pub fn transport(self, transport: impl Transport + 'static) -> Self {
+        self.shared_transport(Some(Rc::new(transport)))
+    }
+
+    pub(crate) fn shared_transport(mut self, transport: Option<Rc<dyn Transport>>) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    pub(crate) fn interceptors(mut self, interceptors: Vec<Rc<dyn Interceptor>>) -> Self {
+        self.interceptors = interceptors;
+        self
+    }
     /// Builds the request and send it to the server, returning the received response.
     pub async fn send(self) -> Result<Response, Error> {
         let req: Request = self.try_into()?;
@@ -208,7 +416,16 @@ impl TryFrom<RequestBuilder> for Request {
         let request = web_sys::Request::new_with_str_and_init(&final_url, &value.options)
             .map_err(js_to_error)?;
 
-        Ok(request.into())
+        Ok(Request {
+            raw: request,
+            body: None,
+            timeout: value.timeout,
+            retry: value.retry,
+            interceptors: value.interceptors,
+            transport: value.transport,
+            #[cfg(feature = "compression")]
+            compression: value.compression,
+        })
     }
 }
 
@@ -219,73 +436,103 @@ impl fmt::Debug for RequestBuilder {
 }
 
 /// The [`Request`] sent to the server
-pub struct Request(web_sys::Request);
+pub struct Request {
+    pub(crate) raw: web_sys::Request,
+    /// The value the body was created from, kept for transports that cannot send a
+    /// `ReadableStream`.
+    pub(crate) body: Option<JsValue>,
+    pub(crate) timeout: Option<Duration>,
+    retry: Option<RetryPolicy>,
+    interceptors: Vec<Rc<dyn Interceptor>>,
+    transport: Option<Rc<dyn Transport>>,
+    /// The encoding to compress the body with before it is sent.
+    #[cfg(feature = "compression")]
+    compression: Option<ContentEncoding>,
+}
 
 impl Request {
     /// Creates a new [`GET`][Method::GET] `Request` with url.
-    pub fn get(url: &str) -> RequestBuilder {
+    pub fn get(url: impl Into<String>) -> RequestBuilder {
         RequestBuilder::new(url).method(Method::GET)
     }
 
     /// Creates a new [`POST`][Method::POST] `Request` with url.
-    pub fn post(url: &str) -> RequestBuilder {
+    pub fn post(url: impl Into<String>) -> RequestBuilder {
         RequestBuilder::new(url).method(Method::POST)
     }
 
     /// Creates a new [`PUT`][Method::PUT] `Request` with url.
-    pub fn put(url: &str) -> RequestBuilder {
+    pub fn put(url: impl Into<String>) -> RequestBuilder {
         RequestBuilder::new(url).method(Method::PUT)
     }
 
     /// Creates a new [`DELETE`][Method::DELETE] `Request` with url.
-    pub fn delete(url: &str) -> RequestBuilder {
+    pub fn delete(url: impl Into<String>) -> RequestBuilder {
         RequestBuilder::new(url).method(Method::DELETE)
     }
 
     /// Creates a new [`PATCH`][Method::PATCH] `Request` with url.
-    pub fn patch(url: &str) -> RequestBuilder {
+    pub fn patch(url: impl Into<String>) -> RequestBuilder {
         RequestBuilder::new(url).method(Method::PATCH)
     }
 
     /// The URL of the request.
     pub fn url(&self) -> String {
-        self.0.url()
+        self.raw.url()
     }
 
     /// Gets the headers.
     pub fn headers(&self) -> Headers {
-        Headers::from_raw(self.0.headers())
+        Headers::from_raw(self.raw.headers())
     }
 
     /// Has the request body been consumed?
     ///
     /// If true, then any future attempts to consume the body will error.
     pub fn body_used(&self) -> bool {
-        self.0.body_used()
+        self.raw.body_used()
+    }
+
+    fn check_body_unused(&self) -> Result<(), Error> {
+        if self.body_used() {
+            Err(Error::BodyUsed { url: self.url() })
+        } else {
+            Ok(())
+        }
     }
 
     /// Gets the body.
     pub fn body(&self) -> Option<ReadableStream> {
-        self.0.body()
+        self.raw.body()
     }
 
     /// Reads the request to completion, returning it as `FormData`.
     pub async fn form_data(&self) -> Result<FormData, Error> {
-        let promise = self.0.form_data().map_err(js_to_error)?;
+        self.check_body_unused()?;
+        let promise = self.raw.form_data().map_err(js_to_error)?;
         let val = JsFuture::from(promise).await.map_err(js_to_error)?;
         Ok(FormData::from(val))
     }
 
+    /// Reads the request to completion, returning it as a [`Multipart`] form.
+    pub async fn multipart(&self) -> Result<Multipart, Error> {
+        self.form_data().await.map(Multipart::from_raw)
+    }
+
     /// Reads the request to completion, parsing it as JSON.
     #[cfg(feature = "json")]
     #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
     pub async fn json<T: DeserializeOwned>(&self) -> Result<T, Error> {
-        serde_json::from_str::<T>(&self.text().await?).map_err(Error::from)
+        serde_json::from_str::<T>(&self.text().await?).map_err(|error| Error::Decode {
+            url: self.url(),
+            source: Box::new(error),
+        })
     }
 
     /// Reads the reqeust as a String.
     pub async fn text(&self) -> Result<String, Error> {
-        let promise = self.0.text().unwrap();
+        self.check_body_unused()?;
+        let promise = self.raw.text().map_err(js_to_error)?;
         let val = JsFuture::from(promise).await.map_err(js_to_error)?;
         let string = js_sys::JsString::from(val);
         Ok(String::from(&string))
@@ -296,7 +543,8 @@ impl Request {
     /// This works by obtaining the response as an `ArrayBuffer`, creating a `Uint8Array` from it
     /// and then converting it to `Vec<u8>`
     pub async fn binary(&self) -> Result<Vec<u8>, Error> {
-        let promise = self.0.array_buffer().map_err(js_to_error)?;
+        self.check_body_unused()?;
+        let promise = self.raw.array_buffer().map_err(js_to_error)?;
         let array_buffer: ArrayBuffer = JsFuture::from(promise)
             .await
             .map_err(js_to_error)?
@@ -309,51 +557,303 @@ impl Request {
 
     /// Return the read only mode for the request
     pub fn mode(&self) -> RequestMode {
-        self.0.mode()
+        self.raw.mode()
     }
 
     /// Return the parsed method for the request
     pub fn method(&self) -> Method {
-        Method::from_str(self.0.method().as_str()).unwrap()
+        Method::from_str(self.raw.method().as_str()).unwrap()
+    }
+
+    /// Creates a copy of this request, which can be sent independently.
+    ///
+    /// Fails if the body has already been used.
+    pub fn try_clone(&self) -> Result<Request, Error> {
+        self.check_body_unused()?;
+        Ok(Request {
+            raw: web_sys::Request::clone(&self.raw).map_err(js_to_error)?,
+            body: self.body.clone(),
+            timeout: self.timeout,
+            retry: self.retry.clone(),
+            interceptors: self.interceptors.clone(),
+            transport: self.transport.clone(),
+            #[cfg(feature = "compression")]
+            compression: self.compression,
+        })
+    }
+
+    /// Reads the request to completion, converting it into an [`http::Request`].
+    ///
+    /// Only the method, URL, headers and body are carried over; browser specific options like
+    /// the [mode](Self::mode) are dropped.
+    pub async fn into_http(self) -> Result<http::Request<Vec<u8>>, Error> {
+        let uri = http::Uri::try_from(self.url())
+            .map_err(|e| Error::InvalidUrl(format!("{:?}: {}", self.url(), e)))?;
+        let headers = HeaderMap::try_from(&self.headers())?;
+        let body = match self.body() {
+            Some(_) => self.binary().await?,
+            None => Vec::new(),
+        };
+
+        let mut request = http::Request::new(body);
+        *request.method_mut() = self.method();
+        *request.uri_mut() = uri;
+        *request.headers_mut() = headers;
+        Ok(request)
     }
 
     /// Executes the request.
+    ///
+    /// Requests created by a [`Client`](super::Client) are passed through its
+    /// [`Interceptor`]s first. With the `tracing` feature, the request is recorded in a
+    /// `http.request` span and, if it is a same-origin request, sent with a W3C `traceparent`
+    /// header.
     pub async fn send(self) -> Result<Response, Error> {
-        let request = self.0;
-        let global = js_sys::global();
-        let maybe_window =
-            Reflect::get(&global, &JsValue::from_str("Window")).map_err(js_to_error)?;
-        let promise = if !maybe_window.is_undefined() {
-            let window = global.dyn_into::<web_sys::Window>().unwrap();
-            window.fetch_with_request(&request)
-        } else {
-            let maybe_worker = Reflect::get(&global, &JsValue::from_str("WorkerGlobalScope"))
-                .map_err(js_to_error)?;
-            if !maybe_worker.is_undefined() {
-                let worker = global.dyn_into::<web_sys::WorkerGlobalScope>().unwrap();
-                worker.fetch_with_request(&request)
-            } else {
-                panic!("Unsupported JavaScript global context");
+        #[cfg(feature = "tracing")]
+        let span = trace::RequestSpan::start(&self.method(), &self.url(), &self.headers());
+        let sending = self.send_untraced();
+        #[cfg(feature = "tracing")]
+        let sending = span.instrument(sending);
+        let result = sending.await;
+        #[cfg(feature = "tracing")]
+        span.finish(&result);
+        result
+    }
+
+    async fn send_untraced(mut self) -> Result<Response, Error> {
+        self.compress_body().await?;
+        if self.interceptors.is_empty() {
+            return self.dispatch().await;
+        }
+        let interceptors = mem::take(&mut self.interceptors);
+        Next::new(&interceptors).run(self).await
+    }
+
+    /// Compresses the body if [`RequestBuilder::compress`] was used, replacing the underlying
+    /// request.
+    pub(crate) async fn compress_body(&mut self) -> Result<(), Error> {
+        #[cfg(feature = "compression")]
+        if let Some(encoding) = self.compression.take() {
+            // Read from a copy, so that the new request can still be created from the original.
+            let copy = web_sys::Request::clone(&self.raw).map_err(js_to_error)?;
+            if let Some(body) = copy.body() {
+                let compressed = compression::compress(body, encoding).await?;
+                let mut init = web_sys::RequestInit::new();
+                init.body(Some(&compressed));
+                self.raw = web_sys::Request::new_with_request_and_init(&self.raw, &init)
+                    .map_err(js_to_error)?;
+                self.headers().set("Content-Encoding", encoding.as_str());
+                self.body = Some(compressed.into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends the request with its transport, bypassing any interceptors.
+    pub(crate) async fn dispatch(self) -> Result<Response, Error> {
+        let transport = self
+            .transport
+            .clone()
+            .unwrap_or_else(|| Rc::new(FetchTransport));
+        if self.retry.is_none() {
+            return transport.send(self).await;
+        }
+
+        let this = &self;
+        let transport = &*transport;
+        self.retrying(move || async move { transport.send(this.try_clone()?).await })
+            .await
+    }
+
+    /// Sends the request over `XMLHttpRequest` instead of `fetch`, which allows observing upload
+    /// progress.
+    ///
+    /// See [`XhrRequest`] for details.
+    pub fn xhr(self) -> XhrRequest {
+        XhrRequest::new(self)
+    }
+
+    /// Calls `send` until it succeeds or the retry policy of this request gives up.
+    pub(crate) async fn retrying<F, Fut>(&self, mut send: F) -> Result<Response, Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<Response, Error>>,
+    {
+        let (policy, max_attempts) = match &self.retry {
+            Some(policy) => (policy, policy.attempts_for(&self.method())),
+            None => return send().await,
+        };
+
+        let mut attempt = 1;
+        loop {
+            let result = send().await;
+            if attempt >= max_attempts
+                || self.raw.signal().aborted()
+                || !policy.should_retry(&result)
+            {
+                return result;
             }
+            TimeoutFuture::new(clamped_millis(policy.backoff_after(attempt))).await;
+            attempt += 1;
+        }
+    }
+
+    pub(crate) async fn send_attempt(&self, request: &web_sys::Request) -> Result<Response, Error> {
+        match self.timeout {
+            Some(timeout) => fetch_with_timeout(request, timeout).await,
+            None => fetch(request).await,
+        }
+    }
+}
+
+/// Converts `duration` to milliseconds, clamped to the largest delay `setTimeout` accepts.
+pub(crate) fn clamped_millis(duration: Duration) -> u32 {
+    duration.as_millis().min(i32::MAX as u128) as u32
+}
+
+/// Calls the `fetch` function of the JavaScript global scope.
+///
+/// Any global exposing `fetch` is supported: windows, all kinds of workers and worklets, as well
+/// as runtimes like Node.js or Deno.
+async fn fetch(request: &web_sys::Request) -> Result<Response, Error> {
+    let global = js_sys::global();
+    let fetch = Reflect::get(&global, &JsValue::from_str("fetch"))
+        .map_err(js_to_error)?
+        .dyn_into::<Function>()
+        .map_err(|_| Error::FetchUnavailable)?;
+    let promise = fetch.call1(&global, request).map_err(js_to_error)?;
+    let promise = Promise::resolve(&promise);
+
+    let response = JsFuture::from(promise)
+        .await
+        .map_err(|error| fetch_error(request, error))?;
+    response
+        .dyn_into::<web_sys::Response>()
+        .map_err(|e| panic!("fetch returned {:?}, not `Response` - this is a bug", e))
+        .map(Response::from)
+}
+
+/// Converts the reason `fetch` rejected with into an [`Error::Aborted`] or [`Error::Network`].
+fn fetch_error(request: &web_sys::Request, error: JsValue) -> Error {
+    let method = Method::from_str(&request.method()).unwrap_or_default();
+    let url = request.url();
+    let error = js_to_js_error(error);
+    if error.name == "AbortError" {
+        Error::Aborted { method, url }
+    } else {
+        Error::Network {
+            method,
+            url,
+            source: Box::new(error),
+        }
+    }
+}
+
+/// Sends `request` with a fresh abort signal that fires once `timeout` elapses.
+///
+/// The signal `request` was built with is forwarded, so aborting it still aborts the fetch.
+async fn fetch_with_timeout(
+    request: &web_sys::Request,
+    timeout: Duration,
+) -> Result<Response, Error> {
+    let controller = AbortController::new().map_err(js_to_error)?;
+    let _forward = ForwardAbort::new(&request.signal(), &controller)?;
+
+    let mut init = web_sys::RequestInit::new();
+    init.signal(Some(&controller.signal()));
+    let request =
+        web_sys::Request::new_with_request_and_init(request, &init).map_err(js_to_error)?;
+
+    let mut timer = TimeoutFuture::new(clamped_millis(timeout));
+    let mut response = Box::pin(fetch(&request));
+
+    let response = poll_fn(|cx| {
+        if let Poll::Ready(response) = response.as_mut().poll(cx) {
+            return Poll::Ready(Some(response));
+        }
+        Pin::new(&mut timer).poll(cx).map(|()| None)
+    })
+    .await;
+
+    match response {
+        Some(response) => response,
+        None => {
+            controller.abort();
+            Err(Error::Timeout(timeout))
+        }
+    }
+}
+
+/// Aborts `controller` when `signal` is aborted, for as long as this value is alive.
+struct ForwardAbort {
+    signal: AbortSignal,
+    callback: Closure<dyn FnMut()>,
+}
+
+impl ForwardAbort {
+    fn new(signal: &AbortSignal, controller: &AbortController) -> Result<Self, Error> {
+        if signal.aborted() {
+            controller.abort();
+        }
+        let callback = {
+            let controller = controller.clone();
+            Closure::wrap(Box::new(move || controller.abort()) as Box<dyn FnMut()>)
         };
+        signal
+            .add_event_listener_with_callback("abort", callback.as_ref().unchecked_ref())
+            .map_err(js_to_error)?;
+        Ok(Self {
+            signal: signal.clone(),
+            callback,
+        })
+    }
+}
 
-        let response = JsFuture::from(promise).await.map_err(js_to_error)?;
-        response
-            .dyn_into::<web_sys::Response>()
-            .map_err(|e| panic!("fetch returned {:?}, not `Response` - this is a bug", e))
-            .map(Response::from)
+impl Drop for ForwardAbort {
+    fn drop(&mut self) {
+        let _ = self
+            .signal
+            .remove_event_listener_with_callback("abort", self.callback.as_ref().unchecked_ref());
     }
 }
 
 impl From<web_sys::Request> for Request {
     fn from(raw: web_sys::Request) -> Self {
-        Request(raw)
+        Request {
+            raw,
+            body: None,
+            timeout: None,
+            retry: None,
+            interceptors: Vec::new(),
+            transport: None,
+            #[cfg(feature = "compression")]
+            compression: None,
+        }
+    }
+}
+
+/// Builds a request with the method, URI, headers and body of an [`http::Request`].
+///
+/// As with [`Request::get`], the URI may be relative to the current location. An empty body is
+/// not sent at all, because `fetch` does not allow `GET` and `HEAD` requests to have one.
+impl<B: AsRef<[u8]>> TryFrom<http::Request<B>> for Request {
+    type Error = crate::error::Error;
+
+    fn try_from(request: http::Request<B>) -> Result<Self, Self::Error> {
+        let (parts, body) = request.into_parts();
+        let builder = RequestBuilder::new(parts.uri.to_string())
+            .method(parts.method)
+            .headers(Headers::try_from(&parts.headers)?);
+        match body.as_ref() {
+            [] => builder.build(),
+            body => builder.body(Uint8Array::from(body)),
+        }
     }
 }
 
 impl From<Request> for web_sys::Request {
     fn from(val: Request) -> Self {
-        val.0
+        val.raw
     }
 }
 
@@ -1,14 +1,21 @@
-use std::{convert::From, fmt};
+use std::convert::{From, TryFrom};
+use std::fmt;
 
 use crate::{js_to_error, Error};
-use js_sys::{ArrayBuffer, Uint8Array};
+use http::{HeaderMap, StatusCode};
+use js_sys::{ArrayBuffer, Reflect, Uint8Array};
 use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::JsFuture;
 use web_sys::ResponseInit;
 
-use crate::http::Headers;
+#[cfg(feature = "serde")]
+use crate::http::codec::{self, BodyCodec};
+#[cfg(feature = "compression")]
+use crate::http::compression::{self, ContentEncoding, Decompressed};
 #[cfg(feature = "json")]
-#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+use crate::http::JsonLines;
+use crate::http::{BodyStream, Headers, Multipart, Progress, ProgressStream};
+#[cfg(feature = "serde")]
 use serde::de::DeserializeOwned;
 
 /// The [`Request`]'s response
@@ -61,6 +68,36 @@ impl Response {
         self.0.ok()
     }
 
+    /// Turns a response with an unsuccessful status code into an [`Error::Status`].
+    ///
+    /// Responses with a status code in the range `200 - 299` are returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use gloo_net::http::Request;
+    /// # async fn no_run() -> Result<(), gloo_net::Error> {
+    /// let body = Request::get("/path")
+    ///     .send()
+    ///     .await?
+    ///     .error_for_status()?
+    ///     .text()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn error_for_status(self) -> Result<Self, Error> {
+        if self.ok() {
+            Ok(self)
+        } else {
+            Err(Error::Status {
+                url: self.url(),
+                status: self.status(),
+                status_text: self.status_text(),
+            })
+        }
+    }
+
     /// The status message corresponding to the
     /// [HTTP status code](https://developer.mozilla.org/en-US/docs/Web/HTTP/Status) from
     /// `Response::status`.
@@ -83,28 +120,249 @@ impl Response {
         self.0.body_used()
     }
 
+    fn check_body_unused(&self) -> Result<(), Error> {
+        if self.body_used() {
+            Err(Error::BodyUsed { url: self.url() })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Gives a response built with a [`ResponseBuilder`], which has no URL, the URL `url`.
+    pub(crate) fn with_url(self, url: &str) -> Result<Self, Error> {
+        // An own property shadows the `url` getter of `Response.prototype`.
+        let descriptor = js_sys::Object::new();
+        Reflect::set(&descriptor, &"value".into(), &url.into()).map_err(js_to_error)?;
+        Reflect::define_property(&self.0, &"url".into(), &descriptor).map_err(js_to_error)?;
+        Ok(self)
+    }
+
+    /// Creates a copy of this response, whose body can be read independently.
+    ///
+    /// Fails if the body has already been used.
+    pub fn try_clone(&self) -> Result<Response, Error> {
+        self.check_body_unused()?;
+        self.0.clone().map(Self).map_err(js_to_error)
+    }
+
     /// Gets the body.
     pub fn body(&self) -> Option<web_sys::ReadableStream> {
         self.0.body()
     }
 
+    /// Reads the body incrementally as a [`Stream`](futures_core::Stream) of byte chunks.
+    ///
+    /// Unlike [`binary`](Self::binary), this does not wait for the whole body to arrive, which
+    /// makes it suitable for large downloads. Dropping the stream cancels the download.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use gloo_net::http::Request;
+    /// # use futures::StreamExt;
+    /// # async fn no_run() -> Result<(), gloo_net::Error> {
+    /// let resp = Request::get("/large-file").send().await?;
+    /// let mut body = resp.bytes_stream();
+    /// while let Some(chunk) = body.next().await {
+    ///     let chunk = chunk?;
+    ///     // process `chunk`...
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bytes_stream(&self) -> BodyStream {
+        BodyStream::new(self.0.body())
+    }
+
+    /// Like [`bytes_stream`](Self::bytes_stream), but calls `on_progress` every time a chunk
+    /// arrives.
+    ///
+    /// The reported total is taken from the `Content-Length` header, if the server sent one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use gloo_net::http::Request;
+    /// # use futures::TryStreamExt;
+    /// # async fn no_run() -> Result<(), gloo_net::Error> {
+    /// let resp = Request::get("/large-file").send().await?;
+    /// let body: Vec<Vec<u8>> = resp
+    ///     .bytes_stream_with_progress(|progress| {
+    ///         if let Some(fraction) = progress.fraction() {
+    ///             // update the progress bar...
+    ///         }
+    ///     })
+    ///     .try_collect()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bytes_stream_with_progress(
+        &self,
+        on_progress: impl FnMut(Progress) + 'static,
+    ) -> ProgressStream {
+        ProgressStream::new(self.bytes_stream(), self.content_length(), on_progress)
+    }
+
+    /// Reads the body incrementally as newline-delimited JSON (also known as NDJSON or JSON
+    /// Lines), yielding every record as soon as it has arrived.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use gloo_net::http::Request;
+    /// # use futures::StreamExt;
+    /// # async fn no_run() -> Result<(), gloo_net::Error> {
+    /// #[derive(serde::Deserialize)]
+    /// struct LogEntry {
+    ///     message: String,
+    /// }
+    ///
+    /// let resp = Request::get("/logs/tail").send().await?;
+    /// let mut entries = resp.json_lines::<LogEntry>();
+    /// while let Some(entry) = entries.next().await {
+    ///     let entry = entry?;
+    ///     // process `entry`...
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn json_lines<T: DeserializeOwned>(&self) -> JsonLines<T> {
+        JsonLines::new(self.bytes_stream(), self.url())
+    }
+
+    /// Decompresses a body which the browser did not decode by itself, returning a response with
+    /// the decompressed body.
+    ///
+    /// Browsers transparently decode bodies sent with a `Content-Encoding` header. This is meant
+    /// for the remaining cases, such as `.gz` files served as `application/gzip`. The body is
+    /// decompressed with `DecompressionStream` as it is read where the browser supports it, and
+    /// read completely and decompressed in Rust otherwise. The `Content-Encoding` and
+    /// `Content-Length` headers are dropped and, as for any constructed response, the
+    /// [`url`](Self::url) is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use gloo_net::http::{ContentEncoding, Request};
+    /// # async fn no_run() -> Result<(), gloo_net::Error> {
+    /// let log = Request::get("/logs/yesterday.log.gz")
+    ///     .send()
+    ///     .await?
+    ///     .decompress(ContentEncoding::Gzip)
+    ///     .await?
+    ///     .text()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "compression")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "compression")))]
+    pub async fn decompress(self, encoding: ContentEncoding) -> Result<Response, Error> {
+        self.check_body_unused()?;
+        let headers = Headers::new();
+        for (name, value) in self.headers().entries() {
+            if name != "content-encoding" && name != "content-length" {
+                headers.append(&name, &value);
+            }
+        }
+        let builder = Response::builder()
+            .status(self.status())
+            .status_text(&self.status_text())
+            .headers(headers);
+
+        let body = match self.body() {
+            Some(body) => body,
+            None => return builder.body(None::<&str>),
+        };
+        match compression::decompress(body, encoding, &self.url()).await? {
+            Decompressed::Stream(stream) => builder.body(Some(&stream)),
+            Decompressed::Bytes(mut bytes) => builder.body(Some(bytes.as_mut_slice())),
+        }
+    }
+
+    /// The value of the `Content-Length` header, if present and valid.
+    pub fn content_length(&self) -> Option<u64> {
+        self.headers().get("Content-Length")?.trim().parse().ok()
+    }
+
     /// Reads the response to completion, returning it as `FormData`.
     pub async fn form_data(&self) -> Result<web_sys::FormData, Error> {
+        self.check_body_unused()?;
         let promise = self.0.form_data().map_err(js_to_error)?;
         let val = JsFuture::from(promise).await.map_err(js_to_error)?;
         Ok(web_sys::FormData::from(val))
     }
 
+    /// Reads the response to completion, returning it as a [`Multipart`] form.
+    pub async fn multipart(&self) -> Result<Multipart, Error> {
+        self.form_data().await.map(Multipart::from_raw)
+    }
+
+    /// Reads the response to completion, converting it into an [`http::Response`].
+    pub async fn into_http(self) -> Result<http::Response<Vec<u8>>, Error> {
+        let status = StatusCode::from_u16(self.status())
+            .map_err(|e| Error::GlooError(format!("invalid status {}: {}", self.status(), e)))?;
+        let headers = HeaderMap::try_from(&self.headers())?;
+        let body = match self.body() {
+            Some(_) => self.binary().await?,
+            None => Vec::new(),
+        };
+
+        let mut response = http::Response::new(body);
+        *response.status_mut() = status;
+        *response.headers_mut() = headers;
+        Ok(response)
+    }
+
+    /// Reads the response to completion, decoding it with `codec`.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub async fn decode<C, T>(&self, codec: C) -> Result<T, Error>
+    where
+        C: BodyCodec,
+        T: DeserializeOwned,
+    {
+        let body = self.binary().await?;
+        codec.decode(&body).map_err(|source| Error::Decode {
+            url: self.url(),
+            source,
+        })
+    }
+
+    /// Reads the response to completion, decoding it with the enabled [`BodyCodec`] that matches
+    /// its `Content-Type` header.
+    ///
+    /// Fails with [`Error::Decode`] if no enabled codec matches.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub async fn decode_negotiated<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        let content_type = self.headers().get("Content-Type").unwrap_or_default();
+        let body = self.binary().await?;
+        let result = codec::decode_negotiated(&content_type, &body)
+            .unwrap_or_else(|| Err(format!("unsupported content type {:?}", content_type).into()));
+        result.map_err(|source| Error::Decode {
+            url: self.url(),
+            source,
+        })
+    }
+
     /// Reads the response to completion, parsing it as JSON.
     #[cfg(feature = "json")]
     #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
     pub async fn json<T: DeserializeOwned>(&self) -> Result<T, Error> {
-        serde_json::from_str::<T>(&self.text().await?).map_err(Error::from)
+        serde_json::from_str::<T>(&self.text().await?).map_err(|error| Error::Decode {
+            url: self.url(),
+            source: Box::new(error),
+        })
     }
 
     /// Reads the response as a String.
     pub async fn text(&self) -> Result<String, Error> {
-        let promise = self.0.text().unwrap();
+        self.check_body_unused()?;
+        let promise = self.0.text().map_err(js_to_error)?;
         let val = JsFuture::from(promise).await.map_err(js_to_error)?;
         let string = js_sys::JsString::from(val);
         Ok(String::from(&string))
@@ -115,6 +373,7 @@ impl Response {
     /// This works by obtaining the response as an `ArrayBuffer`, creating a `Uint8Array` from it
     /// and then converting it to `Vec<u8>`
     pub async fn binary(&self) -> Result<Vec<u8>, Error> {
+        self.check_body_unused()?;
         let promise = self.0.array_buffer().map_err(js_to_error)?;
         let array_buffer: ArrayBuffer = JsFuture::from(promise)
             .await
@@ -133,6 +392,26 @@ impl From<web_sys::Response> for Response {
     }
 }
 
+/// Builds a response with the status, headers and body of an [`http::Response`].
+///
+/// The status text is the canonical reason phrase of the status code. An empty body is treated
+/// as no body, because responses with statuses like `204 No Content` must not have one.
+impl<B: AsRef<[u8]>> TryFrom<http::Response<B>> for Response {
+    type Error = Error;
+
+    fn try_from(response: http::Response<B>) -> Result<Self, Self::Error> {
+        let (parts, body) = response.into_parts();
+        let builder = ResponseBuilder::new()
+            .status(parts.status.as_u16())
+            .status_text(parts.status.canonical_reason().unwrap_or(""))
+            .headers(Headers::try_from(&parts.headers)?);
+        match body.as_ref() {
+            [] => builder.body(None::<&str>),
+            body => builder.body(Some::<&js_sys::Object>(&Uint8Array::from(body))),
+        }
+    }
+}
+
 impl From<Response> for web_sys::Response {
     fn from(res: Response) -> Self {
         res.0
@@ -0,0 +1,145 @@
+use crate::http::Response;
+use crate::Error;
+use http::Method;
+use std::fmt;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// Describes when and how often a failed request is sent again.
+///
+/// Attach it to a request with [`RequestBuilder::retry`](super::RequestBuilder::retry). A request
+/// is retried when `fetch` fails with a [network error](Error::Network) or a
+/// [timeout](Error::Timeout), or when the response status matches [`retry_on`](Self::retry_on).
+/// Requests aborted through their abort signal are never retried.
+///
+/// Between attempts the policy waits with exponential backoff: the `n`th retry waits
+/// `initial_backoff * 2^(n - 1)`, capped at `max_backoff`. With jitter enabled (the default) the
+/// actual delay is picked randomly between half and all of that value, so that many clients
+/// failing at once do not retry in lockstep.
+///
+/// Only idempotent methods (`GET`, `HEAD`, `PUT`, `DELETE`, `OPTIONS` and `TRACE`) are retried
+/// unless [`retry_non_idempotent`](Self::retry_non_idempotent) is set.
+///
+/// # Example
+///
+/// ```
+/// # fn no_run() {
+/// use gloo_net::http::{Request, RetryPolicy};
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::new()
+///     .max_attempts(5)
+///     .backoff(Duration::from_millis(200), Duration::from_secs(5))
+///     .retry_on(|status| status == 503);
+///
+/// let request = Request::get("/flaky").retry(policy);
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    retry_on: Rc<dyn Fn(u16) -> bool>,
+    retry_non_idempotent: bool,
+}
+
+impl RetryPolicy {
+    /// Creates a policy that makes up to 3 attempts, starting with a 100ms backoff capped at 10s,
+    /// and retries on `408`, `429`, `500`, `502`, `503` and `504` responses.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The total number of attempts, including the first one.
+    ///
+    /// A value of `1` disables retrying. `0` is treated as `1`.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// The delay before the first retry and the upper bound for any delay.
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Whether to randomise the delay between attempts.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets the predicate deciding which response statuses are retried.
+    pub fn retry_on(mut self, retry_on: impl Fn(u16) -> bool + 'static) -> Self {
+        self.retry_on = Rc::new(retry_on);
+        self
+    }
+
+    /// Whether to also retry requests whose method is not idempotent, such as `POST` and `PATCH`.
+    ///
+    /// Only enable this if the server can safely handle the same request more than once.
+    pub fn retry_non_idempotent(mut self, retry_non_idempotent: bool) -> Self {
+        self.retry_non_idempotent = retry_non_idempotent;
+        self
+    }
+
+    pub(crate) fn attempts_for(&self, method: &Method) -> u32 {
+        if self.retry_non_idempotent || method.is_idempotent() {
+            self.max_attempts
+        } else {
+            1
+        }
+    }
+
+    pub(crate) fn should_retry(&self, result: &Result<Response, Error>) -> bool {
+        match result {
+            Ok(response) => (self.retry_on)(response.status()),
+            Err(Error::Network { .. }) | Err(Error::Timeout(_)) => true,
+            Err(_) => false,
+        }
+    }
+
+    /// The delay to wait after the `attempt`th attempt failed.
+    pub(crate) fn backoff_after(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        let delay = self
+            .initial_backoff
+            .checked_mul(factor)
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff);
+        if self.jitter {
+            delay / 2 + delay.mul_f64(js_sys::Math::random() / 2.0)
+        } else {
+            delay
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            jitter: true,
+            retry_on: Rc::new(|status| matches!(status, 408 | 429 | 500 | 502 | 503 | 504)),
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("jitter", &self.jitter)
+            .field("retry_non_idempotent", &self.retry_non_idempotent)
+            .finish_non_exhaustive()
+    }
+}
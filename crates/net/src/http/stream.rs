@@ -0,0 +1,164 @@
+use crate::{js_to_error, Error};
+use futures_core::Stream;
+use js_sys::{Array, Function, Object, Promise, Reflect, Uint8Array};
+use std::cell::RefCell;
+use std::fmt;
+use std::future::{poll_fn, Future};
+use std::mem;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+use web_sys::{ReadableStream, ReadableStreamDefaultController, ReadableStreamDefaultReader};
+
+/// A body read incrementally as a [`Stream`] of byte chunks.
+///
+/// Created by [`Response::bytes_stream`](super::Response::bytes_stream). The chunks are yielded
+/// as the browser receives them, so the body never has to be held in memory at once.
+///
+/// Dropping the stream before it has finished cancels the underlying `ReadableStream`, which
+/// stops the download.
+pub struct BodyStream {
+    state: State,
+}
+
+enum State {
+    Reading {
+        reader: ReadableStreamDefaultReader,
+        read: Option<JsFuture>,
+    },
+    Failed(Error),
+    Done,
+}
+
+impl BodyStream {
+    pub(crate) fn new(body: Option<ReadableStream>) -> Self {
+        let state = match body.map(|body| ReadableStreamDefaultReader::new(&body)) {
+            Some(Ok(reader)) => State::Reading { reader, read: None },
+            Some(Err(e)) => State::Failed(js_to_error(e)),
+            None => State::Done,
+        };
+        Self { state }
+    }
+}
+
+impl Stream for BodyStream {
+    type Item = Result<Vec<u8>, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let (reader, mut read) = match mem::replace(&mut self.state, State::Done) {
+            State::Reading { reader, read } => (reader, read),
+            State::Failed(e) => return Poll::Ready(Some(Err(e))),
+            State::Done => return Poll::Ready(None),
+        };
+
+        let result =
+            match Pin::new(read.get_or_insert_with(|| JsFuture::from(reader.read()))).poll(cx) {
+                Poll::Ready(result) => result,
+                Poll::Pending => {
+                    self.state = State::Reading { reader, read };
+                    return Poll::Pending;
+                }
+            };
+        match result.and_then(parse_chunk) {
+            Ok(Some(chunk)) => {
+                self.state = State::Reading { reader, read: None };
+                Poll::Ready(Some(Ok(chunk)))
+            }
+            Ok(None) => Poll::Ready(None),
+            Err(e) => Poll::Ready(Some(Err(js_to_error(e)))),
+        }
+    }
+}
+
+/// Extracts the chunk from a `ReadableStreamReadResult`, returning `None` once the stream is done.
+fn parse_chunk(result: JsValue) -> Result<Option<Vec<u8>>, JsValue> {
+    if Reflect::get(&result, &JsValue::from_str("done"))?.is_truthy() {
+        return Ok(None);
+    }
+    let value = Reflect::get(&result, &JsValue::from_str("value"))?;
+    Ok(Some(value.unchecked_into::<Uint8Array>().to_vec()))
+}
+
+impl Drop for BodyStream {
+    fn drop(&mut self) {
+        if let State::Reading { reader, .. } = &self.state {
+            // Any pending read resolves as done once the stream is cancelled.
+            let _ = reader.cancel();
+        }
+    }
+}
+
+impl fmt::Debug for BodyStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = match self.state {
+            State::Reading { .. } => "Reading",
+            State::Failed(_) => "Failed",
+            State::Done => "Done",
+        };
+        f.debug_struct("BodyStream").field("state", &state).finish()
+    }
+}
+
+type SharedStream = Rc<RefCell<Option<Pin<Box<dyn Stream<Item = Vec<u8>>>>>>>;
+
+/// Exposes `stream` to JavaScript as a `ReadableStream` of `Uint8Array` chunks.
+///
+/// Chunks are only pulled from `stream` when the consumer asks for more data. `stream` is dropped
+/// once it is exhausted or the `ReadableStream` is cancelled.
+pub(crate) fn readable_stream_from<S>(stream: S) -> Result<ReadableStream, Error>
+where
+    S: Stream<Item = Vec<u8>> + 'static,
+{
+    let stream: SharedStream = Rc::new(RefCell::new(Some(Box::pin(stream))));
+
+    let pull = {
+        let stream = stream.clone();
+        Closure::wrap(
+            Box::new(move |controller: ReadableStreamDefaultController| {
+                let stream = stream.clone();
+                future_to_promise(async move {
+                    let chunk = poll_fn(|cx| match stream.borrow_mut().as_mut() {
+                        Some(stream) => stream.as_mut().poll_next(cx),
+                        None => Poll::Ready(None),
+                    })
+                    .await;
+                    match chunk {
+                        Some(chunk) => {
+                            controller.enqueue_with_chunk(&Uint8Array::from(chunk.as_slice()))?
+                        }
+                        None => {
+                            stream.borrow_mut().take();
+                            controller.close()?;
+                        }
+                    }
+                    Ok(JsValue::UNDEFINED)
+                })
+            }) as Box<dyn FnMut(ReadableStreamDefaultController) -> Promise>,
+        )
+    };
+    let cancel = Closure::wrap(Box::new(move |_reason: JsValue| {
+        stream.borrow_mut().take();
+    }) as Box<dyn FnMut(JsValue)>);
+
+    let source = Object::new();
+    Reflect::set(&source, &JsValue::from_str("pull"), &pull.into_js_value())
+        .map_err(js_to_error)?;
+    Reflect::set(
+        &source,
+        &JsValue::from_str("cancel"),
+        &cancel.into_js_value(),
+    )
+    .map_err(js_to_error)?;
+    // `ReadableStream::new_with_underlying_source` is behind `web_sys_unstable_apis`, so the
+    // constructor is called through reflection instead.
+    let constructor: Function =
+        Reflect::get(&js_sys::global(), &JsValue::from_str("ReadableStream"))
+            .map_err(js_to_error)?
+            .unchecked_into();
+    Reflect::construct(&constructor, &Array::of1(&source))
+        .map(JsCast::unchecked_into)
+        .map_err(js_to_error)
+}
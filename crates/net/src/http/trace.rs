@@ -0,0 +1,165 @@
+use super::{Headers, Response};
+use crate::Error;
+use http::Method;
+use std::future::Future;
+use tracing::field::Empty;
+use tracing::instrument::Instrumented;
+use tracing::{Instrument, Span};
+
+/// The `tracing` span of a request, from before the interceptors run until the response headers
+/// have arrived.
+///
+/// The span carries the W3C trace context of the request. Unless the request already has a
+/// `traceparent` header, one is added with a new trace id and the id of this span as the parent,
+/// so that the server can continue the trace. In the browser, the header is only added to
+/// same-origin requests: it is not CORS-safelisted and would make every cross-origin request
+/// send a preflight request first.
+pub(crate) struct RequestSpan {
+    span: Span,
+    start: Stopwatch,
+}
+
+impl RequestSpan {
+    /// Starts the span of a request, adding a `traceparent` header to `headers` if needed.
+    pub(crate) fn start(method: &Method, url: &str, headers: &Headers) -> Self {
+        let span_id = random_id().max(1);
+        let trace_id = match headers.get("traceparent").as_deref().and_then(trace_id) {
+            Some(trace_id) => trace_id.to_string(),
+            None => {
+                let trace_id = format!("{:016x}{:016x}", random_id(), random_id().max(1));
+                if propagates_to(url) {
+                    headers.set("traceparent", &format!("00-{trace_id}-{span_id:016x}-01"));
+                }
+                trace_id
+            }
+        };
+        let span = tracing::info_span!(
+            "http.request",
+            http.request.method = %method,
+            url.full = %url,
+            trace_id = %trace_id,
+            span_id = %format_args!("{span_id:016x}"),
+            http.response.status_code = Empty,
+            http.response.body.size = Empty,
+        );
+        Self {
+            span,
+            start: Stopwatch::start(),
+        }
+    }
+
+    /// Runs `future` inside of the span.
+    pub(crate) fn instrument<F: Future>(&self, future: F) -> Instrumented<F> {
+        future.instrument(self.span.clone())
+    }
+
+    /// Records the outcome of the request and closes the span.
+    pub(crate) fn finish(self, result: &Result<Response, Error>) {
+        let duration_ms = self.start.elapsed_ms();
+        let _entered = self.span.enter();
+        match result {
+            Ok(response) => {
+                let size = response
+                    .headers()
+                    .get("Content-Length")
+                    .and_then(|length| length.parse::<u64>().ok());
+                self.span
+                    .record("http.response.status_code", response.status());
+                if let Some(size) = size {
+                    self.span.record("http.response.body.size", size);
+                }
+                tracing::debug!(
+                    status = response.status(),
+                    duration_ms,
+                    bytes = size,
+                    "response received"
+                );
+            }
+            Err(error) => tracing::warn!(%error, duration_ms, "request failed"),
+        }
+    }
+}
+
+/// Extracts the trace id of a `traceparent` header, if it is well-formed.
+fn trace_id(traceparent: &str) -> Option<&str> {
+    let mut parts = traceparent.trim().split('-');
+    let (_version, trace_id) = (parts.next()?, parts.next()?);
+    let valid = trace_id.len() == 32
+        && trace_id.bytes().all(|byte| byte.is_ascii_hexdigit())
+        && trace_id.bytes().any(|byte| byte != b'0');
+    valid.then_some(trace_id)
+}
+
+/// Whether a `traceparent` header may be sent to `url`, i.e. whether it has the origin of the
+/// page or worker.
+#[cfg(target_arch = "wasm32")]
+fn propagates_to(url: &str) -> bool {
+    use js_sys::Reflect;
+    use wasm_bindgen::JsValue;
+
+    let origin = Reflect::get(&js_sys::global(), &JsValue::from_str("location"))
+        .and_then(|location| Reflect::get(&location, &JsValue::from_str("origin")))
+        .ok()
+        .and_then(|origin| origin.as_string());
+    match (origin, web_sys::Url::new(url)) {
+        (Some(origin), Ok(url)) => origin != "null" && url.origin() == origin,
+        _ => false,
+    }
+}
+
+/// Whether a `traceparent` header may be sent to `url`. Without CORS, it always may.
+#[cfg(not(target_arch = "wasm32"))]
+fn propagates_to(_url: &str) -> bool {
+    true
+}
+
+/// Measures how long a request takes. `std::time::Instant` is not available in the browser.
+struct Stopwatch {
+    #[cfg(target_arch = "wasm32")]
+    start: f64,
+    #[cfg(not(target_arch = "wasm32"))]
+    start: std::time::Instant,
+}
+
+impl Stopwatch {
+    #[cfg(target_arch = "wasm32")]
+    fn start() -> Self {
+        Self {
+            start: js_sys::Date::now(),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn start() -> Self {
+        Self {
+            start: std::time::Instant::now(),
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn elapsed_ms(&self) -> f64 {
+        js_sys::Date::now() - self.start
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn elapsed_ms(&self) -> f64 {
+        self.start.elapsed().as_secs_f64() * 1000.0
+    }
+}
+
+/// A random 64-bit id.
+#[cfg(target_arch = "wasm32")]
+fn random_id() -> u64 {
+    let half = || (js_sys::Math::random() * 4_294_967_296.0) as u64;
+    half() << 32 | half()
+}
+
+/// A random 64-bit id.
+#[cfg(not(target_arch = "wasm32"))]
+fn random_id() -> u64 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+
+    // Every `RandomState` is seeded differently.
+    RandomState::new().build_hasher().finish()
+}
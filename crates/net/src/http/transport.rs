@@ -0,0 +1,32 @@
+use crate::http::{Request, Response};
+use crate::Error;
+use std::future::Future;
+use std::pin::Pin;
+
+/// The future returned by a [`Transport`] or an [`Interceptor`](super::Interceptor).
+pub type SendFuture<'a> = Pin<Box<dyn Future<Output = Result<Response, Error>> + 'a>>;
+
+/// Sends a [`Request`] and produces its [`Response`].
+///
+/// Requests are sent with [`FetchTransport`] unless another transport is set with
+/// [`RequestBuilder::transport`](super::RequestBuilder::transport) or
+/// [`ClientBuilder::transport`](super::ClientBuilder::transport). A transport handles a single
+/// attempt: [retries](super::RequestBuilder::retry) are performed by calling it again with a
+/// copy of the request. Implementing the [timeout](super::RequestBuilder::timeout) is up to the
+/// transport.
+///
+/// See [`MockTransport`](super::MockTransport) for a transport that does not touch the network.
+pub trait Transport {
+    /// Sends `request`.
+    fn send<'a>(&'a self, request: Request) -> SendFuture<'a>;
+}
+
+/// The default [`Transport`], which sends requests with the `fetch` API.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FetchTransport;
+
+impl Transport for FetchTransport {
+    fn send<'a>(&'a self, request: Request) -> SendFuture<'a> {
+        Box::pin(async move { request.send_attempt(&request.raw).await })
+    }
+}
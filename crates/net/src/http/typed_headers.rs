@@ -0,0 +1,484 @@
+use crate::Error;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use std::error::Error as StdError;
+use std::fmt;
+use std::time::Duration;
+
+/// The error returned when a [`TypedHeader`] cannot be parsed or formatted.
+pub type HeaderError = Box<dyn StdError + Send + Sync>;
+
+/// A header whose value is parsed into, and formatted from, a Rust type.
+///
+/// Used with [`Headers::typed_get`](super::Headers::typed_get) and
+/// [`Headers::typed_set`](super::Headers::typed_set), which report invalid values as
+/// [`Error::InvalidHeader`]. Implementations are provided for [`ContentType`],
+/// [`ContentLength`], [`Authorization`], [`CacheControl`], [`ETag`] and [`Range`].
+///
+/// # Example
+///
+/// ```
+/// use gloo_net::http::{HeaderError, Headers, TypedHeader};
+///
+/// struct RequestId(u64);
+///
+/// impl TypedHeader for RequestId {
+///     const NAME: &'static str = "X-Request-Id";
+///
+///     fn decode(value: &str) -> Result<Self, HeaderError> {
+///         Ok(Self(value.trim().parse()?))
+///     }
+///
+///     fn encode(&self) -> Result<String, HeaderError> {
+///         Ok(self.0.to_string())
+///     }
+/// }
+///
+/// # fn no_run() -> Result<(), gloo_net::Error> {
+/// let headers = Headers::new();
+/// headers.typed_set(&RequestId(42))?;
+/// assert_eq!(headers.get("X-Request-Id").as_deref(), Some("42"));
+/// # Ok(())
+/// # }
+/// ```
+pub trait TypedHeader: Sized {
+    /// The name of the header.
+    const NAME: &'static str;
+
+    /// Parses the value of the header.
+    fn decode(value: &str) -> Result<Self, HeaderError>;
+
+    /// Formats the value of the header.
+    fn encode(&self) -> Result<String, HeaderError>;
+}
+
+/// Parses `value` as the header `H`.
+pub(crate) fn decode<H: TypedHeader>(value: &str) -> Result<H, Error> {
+    H::decode(value).map_err(|source| Error::InvalidHeader {
+        name: H::NAME.to_string(),
+        source,
+    })
+}
+
+/// Formats `header`, checking that the result is a valid header value.
+pub(crate) fn encode<H: TypedHeader>(header: &H) -> Result<String, Error> {
+    let invalid = |source| Error::InvalidHeader {
+        name: H::NAME.to_string(),
+        source,
+    };
+    let value = header.encode().map_err(invalid)?;
+    http::HeaderValue::from_str(&value).map_err(|e| invalid(Box::new(e)))?;
+    Ok(value)
+}
+
+/// The `Content-Type` header: the media type of the body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentType(pub mime::Mime);
+
+impl From<mime::Mime> for ContentType {
+    fn from(mime: mime::Mime) -> Self {
+        Self(mime)
+    }
+}
+
+impl TypedHeader for ContentType {
+    const NAME: &'static str = "Content-Type";
+
+    fn decode(value: &str) -> Result<Self, HeaderError> {
+        Ok(Self(value.trim().parse()?))
+    }
+
+    fn encode(&self) -> Result<String, HeaderError> {
+        Ok(self.0.to_string())
+    }
+}
+
+/// The `Content-Length` header: the size of the body in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentLength(pub u64);
+
+impl TypedHeader for ContentLength {
+    const NAME: &'static str = "Content-Length";
+
+    fn decode(value: &str) -> Result<Self, HeaderError> {
+        let value = value.trim();
+        if !value.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(format!("{:?} is not a length", value).into());
+        }
+        Ok(Self(value.parse()?))
+    }
+
+    fn encode(&self) -> Result<String, HeaderError> {
+        Ok(self.0.to_string())
+    }
+}
+
+/// The `Authorization` header: the credentials of the client.
+///
+/// The `Debug` output does not include the credentials.
+#[derive(Clone, PartialEq, Eq)]
+pub enum Authorization {
+    /// A bearer token, such as an OAuth 2.0 access token.
+    Bearer(String),
+    /// A username and password, sent base64 encoded.
+    Basic {
+        /// The username, which must not contain a colon.
+        username: String,
+        /// The password.
+        password: String,
+    },
+}
+
+impl Authorization {
+    /// Creates `Bearer` credentials.
+    pub fn bearer(token: &str) -> Self {
+        Self::Bearer(token.to_string())
+    }
+
+    /// Creates `Basic` credentials.
+    pub fn basic(username: &str, password: &str) -> Self {
+        Self::Basic {
+            username: username.to_string(),
+            password: password.to_string(),
+        }
+    }
+}
+
+impl TypedHeader for Authorization {
+    const NAME: &'static str = "Authorization";
+
+    fn decode(value: &str) -> Result<Self, HeaderError> {
+        let (scheme, credentials) = value.trim().split_once(' ').ok_or("missing credentials")?;
+        let credentials = credentials.trim();
+        if scheme.eq_ignore_ascii_case("bearer") {
+            check_token68(credentials)?;
+            Ok(Self::Bearer(credentials.to_string()))
+        } else if scheme.eq_ignore_ascii_case("basic") {
+            let decoded = String::from_utf8(BASE64.decode(credentials)?)?;
+            let (username, password) = decoded
+                .split_once(':')
+                .ok_or("missing colon between username and password")?;
+            Ok(Self::basic(username, password))
+        } else {
+            Err(format!("unsupported scheme {:?}", scheme).into())
+        }
+    }
+
+    fn encode(&self) -> Result<String, HeaderError> {
+        match self {
+            Self::Bearer(token) => {
+                check_token68(token)?;
+                Ok(format!("Bearer {}", token))
+            }
+            Self::Basic { username, password } => {
+                if username.contains(':') {
+                    return Err("the username contains a colon".into());
+                }
+                let credentials = BASE64.encode(format!("{}:{}", username, password));
+                Ok(format!("Basic {}", credentials))
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Authorization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bearer(_) => f.write_str("Bearer(..)"),
+            Self::Basic { username, .. } => f
+                .debug_struct("Basic")
+                .field("username", username)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+/// Checks the `token68` syntax of bearer tokens.
+fn check_token68(token: &str) -> Result<(), HeaderError> {
+    let body = token.trim_end_matches('=');
+    let valid = !body.is_empty()
+        && body
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"-._~+/".contains(&b));
+    if valid {
+        Ok(())
+    } else {
+        Err("the token contains invalid characters".into())
+    }
+}
+
+/// The `Cache-Control` header: directives for caches along the way.
+///
+/// Unknown directives are ignored when parsing.
+///
+/// # Example
+///
+/// ```
+/// # use gloo_net::http::CacheControl;
+/// # use std::time::Duration;
+/// let cache_control = CacheControl {
+///     public: true,
+///     max_age: Some(Duration::from_secs(3600)),
+///     ..CacheControl::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheControl {
+    /// `no-cache`: stored responses must be revalidated before they are used.
+    pub no_cache: bool,
+    /// `no-store`: the response must not be stored.
+    pub no_store: bool,
+    /// `no-transform`: intermediaries must not modify the body.
+    pub no_transform: bool,
+    /// `must-revalidate`: stale responses must not be used without revalidation.
+    pub must_revalidate: bool,
+    /// `public`: the response may be stored by shared caches.
+    pub public: bool,
+    /// `private`: the response may only be stored by the browser.
+    pub private: bool,
+    /// `immutable`: the response will not change while it is fresh.
+    pub immutable: bool,
+    /// `max-age`: how long the response stays fresh.
+    pub max_age: Option<Duration>,
+    /// `s-maxage`: how long the response stays fresh in shared caches.
+    pub s_maxage: Option<Duration>,
+    /// `stale-while-revalidate`: how long a stale response may be used while it is revalidated.
+    pub stale_while_revalidate: Option<Duration>,
+}
+
+impl TypedHeader for CacheControl {
+    const NAME: &'static str = "Cache-Control";
+
+    fn decode(value: &str) -> Result<Self, HeaderError> {
+        let mut cache_control = Self::default();
+        for directive in value.split(',').map(str::trim) {
+            let (name, argument) = match directive.split_once('=') {
+                Some((name, argument)) => (name.trim(), Some(argument.trim().trim_matches('"'))),
+                None => (directive, None),
+            };
+            let seconds = || -> Result<Duration, HeaderError> {
+                let argument = argument.ok_or_else(|| format!("{} needs a value", name))?;
+                Ok(Duration::from_secs(argument.parse()?))
+            };
+            match name.to_ascii_lowercase().as_str() {
+                "no-cache" => cache_control.no_cache = true,
+                "no-store" => cache_control.no_store = true,
+                "no-transform" => cache_control.no_transform = true,
+                "must-revalidate" => cache_control.must_revalidate = true,
+                "public" => cache_control.public = true,
+                "private" => cache_control.private = true,
+                "immutable" => cache_control.immutable = true,
+                "max-age" => cache_control.max_age = Some(seconds()?),
+                "s-maxage" => cache_control.s_maxage = Some(seconds()?),
+                "stale-while-revalidate" => cache_control.stale_while_revalidate = Some(seconds()?),
+                _ => {}
+            }
+        }
+        Ok(cache_control)
+    }
+
+    fn encode(&self) -> Result<String, HeaderError> {
+        let flags = [
+            (self.no_cache, "no-cache"),
+            (self.no_store, "no-store"),
+            (self.no_transform, "no-transform"),
+            (self.must_revalidate, "must-revalidate"),
+            (self.public, "public"),
+            (self.private, "private"),
+            (self.immutable, "immutable"),
+        ];
+        let durations = [
+            (self.max_age, "max-age"),
+            (self.s_maxage, "s-maxage"),
+            (self.stale_while_revalidate, "stale-while-revalidate"),
+        ];
+        let directives: Vec<_> = flags
+            .iter()
+            .filter(|(set, _)| *set)
+            .map(|(_, name)| name.to_string())
+            .chain(durations.iter().filter_map(|(duration, name)| {
+                duration.map(|duration| format!("{}={}", name, duration.as_secs()))
+            }))
+            .collect();
+        if directives.is_empty() {
+            return Err("no directives are set".into());
+        }
+        Ok(directives.join(", "))
+    }
+}
+
+/// The `ETag` header: an identifier for a specific version of a resource.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ETag {
+    tag: String,
+    weak: bool,
+}
+
+impl ETag {
+    /// Creates a strong entity tag, which changes whenever the body changes.
+    ///
+    /// Fails if `tag` contains quotes, whitespace or control characters.
+    pub fn strong(tag: &str) -> Result<Self, HeaderError> {
+        check_entity_tag(tag)?;
+        Ok(Self {
+            tag: tag.to_string(),
+            weak: false,
+        })
+    }
+
+    /// Creates a weak entity tag, which only changes when the body changes meaningfully.
+    ///
+    /// Fails if `tag` contains quotes, whitespace or control characters.
+    pub fn weak(tag: &str) -> Result<Self, HeaderError> {
+        check_entity_tag(tag)?;
+        Ok(Self {
+            tag: tag.to_string(),
+            weak: true,
+        })
+    }
+
+    /// The tag, without quotes.
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Whether this is a weak entity tag.
+    pub fn is_weak(&self) -> bool {
+        self.weak
+    }
+
+    /// Whether both tags are strong and equal, as required for byte range requests.
+    pub fn strong_eq(&self, other: &ETag) -> bool {
+        !self.weak && !other.weak && self.tag == other.tag
+    }
+
+    /// Whether the tags are equal, regardless of their weakness.
+    pub fn weak_eq(&self, other: &ETag) -> bool {
+        self.tag == other.tag
+    }
+}
+
+impl TypedHeader for ETag {
+    const NAME: &'static str = "ETag";
+
+    fn decode(value: &str) -> Result<Self, HeaderError> {
+        let value = value.trim();
+        let (weak, quoted) = match value.strip_prefix("W/") {
+            Some(quoted) => (true, quoted),
+            None => (false, value),
+        };
+        let tag = quoted
+            .strip_prefix('"')
+            .and_then(|tag| tag.strip_suffix('"'))
+            .ok_or("the tag is not quoted")?;
+        check_entity_tag(tag)?;
+        Ok(Self {
+            tag: tag.to_string(),
+            weak,
+        })
+    }
+
+    fn encode(&self) -> Result<String, HeaderError> {
+        let prefix = if self.weak { "W/" } else { "" };
+        Ok(format!("{}\"{}\"", prefix, self.tag))
+    }
+}
+
+/// Checks that `tag` only contains the characters allowed in an entity tag.
+fn check_entity_tag(tag: &str) -> Result<(), HeaderError> {
+    if tag
+        .chars()
+        .all(|c| c == '!' || ('#'..='~').contains(&c) || !c.is_ascii())
+    {
+        Ok(())
+    } else {
+        Err(format!("{:?} contains characters not allowed in an entity tag", tag).into())
+    }
+}
+
+/// The `Range` header: the parts of the body the client asks for.
+///
+/// # Example
+///
+/// ```
+/// # use gloo_net::http::{ByteRange, Headers, Range};
+/// # fn no_run() -> Result<(), gloo_net::Error> {
+/// let headers = Headers::new();
+/// headers.typed_set(&Range::from(ByteRange::FromTo(0, 1023)))?;
+/// assert_eq!(headers.get("Range").as_deref(), Some("bytes=0-1023"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Range(pub Vec<ByteRange>);
+
+/// One of the ranges of a [`Range`] header, in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteRange {
+    /// The bytes from the first to the second offset, inclusive.
+    FromTo(u64, u64),
+    /// The bytes from the offset to the end.
+    From(u64),
+    /// The given number of bytes at the end.
+    Last(u64),
+}
+
+impl From<ByteRange> for Range {
+    fn from(range: ByteRange) -> Self {
+        Self(vec![range])
+    }
+}
+
+impl TypedHeader for Range {
+    const NAME: &'static str = "Range";
+
+    fn decode(value: &str) -> Result<Self, HeaderError> {
+        let (unit, ranges) = value.trim().split_once('=').ok_or("missing unit")?;
+        if !unit.trim().eq_ignore_ascii_case("bytes") {
+            return Err(format!("unsupported unit {:?}", unit).into());
+        }
+        let ranges = ranges
+            .split(',')
+            .map(|range| -> Result<ByteRange, HeaderError> {
+                let (start, end) = range.trim().split_once('-').ok_or("missing dash")?;
+                let range = match (start, end) {
+                    ("", last) => ByteRange::Last(last.parse()?),
+                    (start, "") => ByteRange::From(start.parse()?),
+                    (start, end) => ByteRange::FromTo(start.parse()?, end.parse()?),
+                };
+                check_byte_range(range)?;
+                Ok(range)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self(ranges))
+    }
+
+    fn encode(&self) -> Result<String, HeaderError> {
+        if self.0.is_empty() {
+            return Err("no ranges are set".into());
+        }
+        let ranges = self
+            .0
+            .iter()
+            .map(|&range| {
+                check_byte_range(range)?;
+                Ok(match range {
+                    ByteRange::FromTo(start, end) => format!("{}-{}", start, end),
+                    ByteRange::From(start) => format!("{}-", start),
+                    ByteRange::Last(length) => format!("-{}", length),
+                })
+            })
+            .collect::<Result<Vec<_>, HeaderError>>()?;
+        Ok(format!("bytes={}", ranges.join(", ")))
+    }
+}
+
+/// Checks that a range does not end before it starts.
+fn check_byte_range(range: ByteRange) -> Result<(), HeaderError> {
+    match range {
+        ByteRange::FromTo(start, end) if end < start => {
+            Err(format!("the range {}-{} ends before it starts", start, end).into())
+        }
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,190 @@
+use crate::Error;
+use std::fmt;
+
+/// A URL assembled from a base URL, path segments, query parameters and a fragment.
+///
+/// Path segments, query parameters and the fragment are percent-encoded, so values such as user
+/// input cannot change the structure of the URL: a segment containing `/` or `?` stays a single
+/// segment. The base URL, and paths passed to [`join`](Self::join), are used as they are.
+///
+/// A `Url` can be passed to [`RequestBuilder::new`](super::RequestBuilder::new) and the
+/// constructors on [`Request`](super::Request).
+///
+/// # Example
+///
+/// ```
+/// # use gloo_net::http::{Request, Url};
+/// # fn no_run() -> Result<(), gloo_net::Error> {
+/// let url = Url::new("https://api.example.com/v1")
+///     .join("/repos")
+///     .segment("rust lang")?
+///     .segment("issues")?
+///     .query_pair("state", "open");
+/// assert_eq!(
+///     url.to_string(),
+///     "https://api.example.com/v1/repos/rust%20lang/issues?state=open"
+/// );
+///
+/// let request = Request::get(url);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Url {
+    path: String,
+    query: String,
+    fragment: Option<String>,
+}
+
+impl Url {
+    /// Starts a URL at `base`, which may already contain a query string and a fragment.
+    pub fn new(base: &str) -> Self {
+        let (rest, fragment) = match base.split_once('#') {
+            Some((rest, fragment)) => (rest, Some(fragment.to_string())),
+            None => (base, None),
+        };
+        let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+        Self {
+            path: path.to_string(),
+            query: query.to_string(),
+            fragment,
+        }
+    }
+
+    /// Appends `path` to the path of the URL, unless it is an absolute `http(s)` URL, which
+    /// replaces it.
+    ///
+    /// This is how [`Client`](super::Client) resolves paths against its base URL: unlike
+    /// relative references in the browser, `Url::new("/api/v1").join("users")` is `/api/v1/users`.
+    /// A query string in `path` is added to the query of the URL, and a fragment replaces it.
+    pub fn join(self, path: &str) -> Self {
+        if is_absolute(path) {
+            return Self::new(path);
+        }
+        let joined = Self::new(path);
+        let mut query = self.query;
+        if !joined.query.is_empty() {
+            if !query.is_empty() {
+                query.push('&');
+            }
+            query.push_str(&joined.query);
+        }
+        Self {
+            path: format!(
+                "{}/{}",
+                self.path.trim_end_matches('/'),
+                joined.path.trim_start_matches('/')
+            ),
+            query,
+            fragment: joined.fragment.or(self.fragment),
+        }
+    }
+
+    /// Appends a percent-encoded path segment.
+    ///
+    /// Fails for empty segments and for `.` and `..`, which would otherwise be resolved to a
+    /// different path.
+    pub fn segment(mut self, segment: &str) -> Result<Self, Error> {
+        if matches!(segment, "" | "." | "..") {
+            return Err(Error::InvalidUrl(format!(
+                "{segment:?} is not allowed as a path segment"
+            )));
+        }
+        if !self.path.ends_with('/') {
+            self.path.push('/');
+        }
+        encode(&mut self.path, segment, is_path_char);
+        Ok(self)
+    }
+
+    /// Appends a query parameter, encoded as `application/x-www-form-urlencoded`.
+    pub fn query_pair(mut self, name: &str, value: &str) -> Self {
+        if !self.query.is_empty() {
+            self.query.push('&');
+        }
+        encode_form(&mut self.query, name);
+        self.query.push('=');
+        encode_form(&mut self.query, value);
+        self
+    }
+
+    /// Appends the fields of `params` as query parameters.
+    ///
+    /// See [`RequestBuilder::serialize_query`](super::RequestBuilder::serialize_query) for how
+    /// values are written.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn serialize_query<T: serde::Serialize + ?Sized>(self, params: &T) -> Result<Self, Error> {
+        let pairs = super::query_serializer::serialize_query(params)?;
+        Ok(pairs
+            .iter()
+            .fold(self, |url, (name, value)| url.query_pair(name, value)))
+    }
+
+    /// Sets the percent-encoded fragment, without the leading `#`.
+    pub fn fragment(mut self, fragment: &str) -> Self {
+        let mut encoded = String::new();
+        encode(&mut encoded, fragment, |byte| {
+            is_path_char(byte) || byte == b'/' || byte == b'?'
+        });
+        self.fragment = Some(encoded);
+        self
+    }
+}
+
+impl fmt::Display for Url {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.path)?;
+        if !self.query.is_empty() {
+            write!(f, "?{}", self.query)?;
+        }
+        if let Some(fragment) = &self.fragment {
+            write!(f, "#{fragment}")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Url> for String {
+    fn from(url: Url) -> Self {
+        url.to_string()
+    }
+}
+
+impl From<&Url> for String {
+    fn from(url: &Url) -> Self {
+        url.to_string()
+    }
+}
+
+fn is_absolute(path: &str) -> bool {
+    path.starts_with("http://") || path.starts_with("https://")
+}
+
+/// Whether `byte` can appear in a path segment without being encoded.
+fn is_path_char(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@".contains(&byte)
+}
+
+/// Appends `input` to `out`, percent-encoding all bytes for which `keep` returns `false`.
+fn encode(out: &mut String, input: &str, keep: impl Fn(u8) -> bool) {
+    for byte in input.bytes() {
+        if keep(byte) {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+    }
+}
+
+/// Appends `input` to `out` as `application/x-www-form-urlencoded`, like `URLSearchParams`.
+fn encode_form(out: &mut String, input: &str) {
+    for byte in input.bytes() {
+        match byte {
+            b' ' => out.push('+'),
+            b'*' | b'-' | b'.' | b'_' => out.push(byte as char),
+            byte if byte.is_ascii_alphanumeric() => out.push(byte as char),
+            byte => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+}
@@ -0,0 +1,341 @@
+use crate::http::request::clamped_millis;
+#[cfg(feature = "tracing")]
+use crate::http::trace;
+use crate::http::{Headers, Progress, Request, Response};
+use crate::{js_to_error, Error};
+use futures_channel::oneshot;
+use gloo_utils::errors::JsError;
+use js_sys::{ArrayBuffer, Object};
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    Blob, EventTarget, FormData, ProgressEvent, RequestCredentials, UrlSearchParams,
+    XmlHttpRequest, XmlHttpRequestResponseType,
+};
+
+type ProgressCallback = Rc<RefCell<dyn FnMut(Progress)>>;
+
+/// A [`Request`] sent over `XMLHttpRequest` rather than `fetch`.
+///
+/// `fetch` cannot report how much of the request body has been uploaded, `XMLHttpRequest` can.
+/// Created by [`Request::xhr`]; everything set on the [`RequestBuilder`](super::RequestBuilder),
+/// including the [timeout](super::RequestBuilder::timeout),
+/// [retry policy](super::RequestBuilder::retry) and [abort
+/// signal](super::RequestBuilder::abort_signal), applies as usual. The `cache`, `mode`,
+/// `redirect`, `referrer` and `integrity` options have no `XMLHttpRequest` equivalent and are
+/// ignored.
+/// [`Interceptor`](super::Interceptor)s of a [`Client`](super::Client) are not run either.
+///
+/// Dropping the future returned by [`send`](Self::send) aborts the request.
+///
+/// The [`Response`] is assembled once the whole body has been received, so its body can be read
+/// without waiting. Its [`url`](Response::url) is the URL after any redirects.
+///
+/// # Example
+///
+/// ```
+/// # use gloo_net::http::Request;
+/// # async fn no_run(file: web_sys::Blob) -> Result<(), gloo_net::Error> {
+/// let resp = Request::post("/upload")
+///     .body(file)?
+///     .xhr()
+///     .on_upload_progress(|progress| {
+///         // update the progress bar...
+///     })
+///     .send()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct XhrRequest {
+    request: Request,
+    on_upload_progress: Option<ProgressCallback>,
+    on_download_progress: Option<ProgressCallback>,
+}
+
+impl XhrRequest {
+    pub(crate) fn new(request: Request) -> Self {
+        Self {
+            request,
+            on_upload_progress: None,
+            on_download_progress: None,
+        }
+    }
+
+    /// Calls `callback` as the request body is uploaded.
+    ///
+    /// When the request is retried, the progress starts over with every attempt.
+    pub fn on_upload_progress(mut self, callback: impl FnMut(Progress) + 'static) -> Self {
+        self.on_upload_progress = Some(Rc::new(RefCell::new(callback)));
+        self
+    }
+
+    /// Calls `callback` as the response body is downloaded.
+    ///
+    /// When the request is retried, the progress starts over with every attempt.
+    pub fn on_download_progress(mut self, callback: impl FnMut(Progress) + 'static) -> Self {
+        self.on_download_progress = Some(Rc::new(RefCell::new(callback)));
+        self
+    }
+
+    /// Executes the request.
+    pub async fn send(self) -> Result<Response, Error> {
+        #[cfg(feature = "tracing")]
+        let span = trace::RequestSpan::start(
+            &self.request.method(),
+            &self.request.url(),
+            &self.request.headers(),
+        );
+        let sending = self.send_untraced();
+        #[cfg(feature = "tracing")]
+        let sending = span.instrument(sending);
+        let result = sending.await;
+        #[cfg(feature = "tracing")]
+        span.finish(&result);
+        result
+    }
+
+    async fn send_untraced(mut self) -> Result<Response, Error> {
+        self.request.compress_body().await?;
+        let body = match (&self.request.body, self.request.raw.body()) {
+            (Some(body), _) => Some(body.clone()),
+            // The body was set through `RequestBuilder::body_stream` or the request was created
+            // from a `web_sys::Request`, so the body is only available as a stream, which
+            // `XMLHttpRequest` cannot send.
+            (None, Some(_)) => {
+                let raw = web_sys::Request::clone(&self.request.raw).map_err(js_to_error)?;
+                let promise = raw.blob().map_err(js_to_error)?;
+                Some(JsFuture::from(promise).await.map_err(js_to_error)?)
+            }
+            (None, None) => None,
+        };
+
+        let this = &self;
+        let body = body.as_ref();
+        self.request
+            .retrying(move || async move { this.send_attempt(body).await })
+            .await
+    }
+
+    async fn send_attempt(&self, body: Option<&JsValue>) -> Result<Response, Error> {
+        let raw = &self.request.raw;
+        let signal = raw.signal();
+        if signal.aborted() {
+            return Err(aborted(&self.request));
+        }
+
+        let xhr = XmlHttpRequest::new().map_err(js_to_error)?;
+        xhr.open_with_async(&raw.method(), &raw.url(), true)
+            .map_err(js_to_error)?;
+        xhr.set_response_type(XmlHttpRequestResponseType::Arraybuffer);
+        xhr.set_with_credentials(raw.credentials() == RequestCredentials::Include);
+        if let Some(timeout) = self.request.timeout {
+            xhr.set_timeout(clamped_millis(timeout));
+        }
+        let is_form_data = body.map_or(false, |body| body.is_instance_of::<FormData>());
+        for (name, value) in Headers::from_raw(raw.headers()).entries() {
+            // `XMLHttpRequest` generates its own multipart boundary for `FormData` bodies.
+            if is_form_data && name.eq_ignore_ascii_case("content-type") {
+                continue;
+            }
+            xhr.set_request_header(&name, &value).map_err(js_to_error)?;
+        }
+
+        let (sender, receiver) = oneshot::channel();
+        let sender = Rc::new(RefCell::new(Some(sender)));
+        let mut listeners = Listeners::default();
+        for (event, outcome) in [
+            ("load", Outcome::Load),
+            ("error", Outcome::Error),
+            ("abort", Outcome::Abort),
+            ("timeout", Outcome::Timeout),
+        ] {
+            let sender = sender.clone();
+            listeners.add(&xhr, event, move |_| {
+                if let Some(sender) = sender.borrow_mut().take() {
+                    let _ = sender.send(outcome);
+                }
+            })?;
+        }
+        if let Some(callback) = &self.on_download_progress {
+            listeners.add_progress(&xhr, callback.clone())?;
+        }
+        if let Some(callback) = &self.on_upload_progress {
+            let upload = xhr.upload().map_err(js_to_error)?;
+            listeners.add_progress(&upload, callback.clone())?;
+        }
+        {
+            let xhr = xhr.clone();
+            listeners.add(&signal, "abort", move |_| {
+                let _ = xhr.abort();
+            })?;
+        }
+
+        let guard = AbortOnDrop(Some(xhr.clone()));
+        send_body(&xhr, body).map_err(js_to_error)?;
+        let outcome = receiver.await.unwrap_or(Outcome::Abort);
+        guard.disarm();
+
+        match outcome {
+            Outcome::Load => response_from_xhr(&xhr, &self.request),
+            Outcome::Error => Err(Error::Network {
+                method: self.request.method(),
+                url: self.request.url(),
+                source: Box::new(JsError::from(js_sys::Error::new("network error"))),
+            }),
+            Outcome::Abort => Err(aborted(&self.request)),
+            Outcome::Timeout => Err(Error::Timeout(self.request.timeout.unwrap_or_default())),
+        }
+    }
+}
+
+impl fmt::Debug for XhrRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("XhrRequest")
+            .field("request", &self.request)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Outcome {
+    Load,
+    Error,
+    Abort,
+    Timeout,
+}
+
+fn aborted(request: &Request) -> Error {
+    Error::Aborted {
+        method: request.method(),
+        url: request.url(),
+    }
+}
+
+fn send_body(xhr: &XmlHttpRequest, body: Option<&JsValue>) -> Result<(), JsValue> {
+    let body = match body {
+        Some(body) => body,
+        None => return xhr.send(),
+    };
+    if let Some(body) = body.as_string() {
+        xhr.send_with_opt_str(Some(&body))
+    } else if let Some(body) = body.dyn_ref::<Blob>() {
+        xhr.send_with_opt_blob(Some(body))
+    } else if let Some(body) = body.dyn_ref::<FormData>() {
+        xhr.send_with_opt_form_data(Some(body))
+    } else if let Some(body) = body.dyn_ref::<UrlSearchParams>() {
+        xhr.send_with_opt_url_search_params(Some(body))
+    } else {
+        // `ArrayBuffer`s and typed arrays.
+        xhr.send_with_opt_buffer_source(Some(body.unchecked_ref::<Object>()))
+    }
+}
+
+/// Builds a [`Response`] from a completed `XMLHttpRequest` for `request`.
+fn response_from_xhr(xhr: &XmlHttpRequest, request: &Request) -> Result<Response, Error> {
+    let status = xhr.status().map_err(js_to_error)?;
+    // The `Response` constructor throws for other statuses, e.g. the informational ones.
+    if !(200..=599).contains(&status) {
+        return Err(Error::Network {
+            method: request.method(),
+            url: request.url(),
+            source: format!("unexpected status {status}").into(),
+        });
+    }
+    let headers = Headers::new();
+    for line in xhr.get_all_response_headers().map_err(js_to_error)?.lines() {
+        if let Some((name, value)) = line.split_once(':') {
+            headers.append(name.trim(), value.trim());
+        }
+    }
+    let builder = Response::builder()
+        .status(status)
+        .status_text(&xhr.status_text().map_err(js_to_error)?)
+        .headers(headers);
+
+    // These statuses must not have a body, the `Response` constructor throws otherwise.
+    let response = if matches!(status, 204 | 205 | 304) {
+        builder.body(None::<&Object>)?
+    } else {
+        let body: ArrayBuffer = xhr.response().map_err(js_to_error)?.unchecked_into();
+        builder.body(Some::<&Object>(&body))?
+    };
+    response.with_url(&xhr.response_url())
+}
+
+/// Event listeners which are removed again when this value is dropped.
+#[derive(Default)]
+struct Listeners(Vec<Listener>);
+
+struct Listener {
+    target: EventTarget,
+    event: &'static str,
+    callback: Closure<dyn FnMut(web_sys::Event)>,
+}
+
+impl Listeners {
+    fn add(
+        &mut self,
+        target: &EventTarget,
+        event: &'static str,
+        callback: impl FnMut(web_sys::Event) + 'static,
+    ) -> Result<(), Error> {
+        let callback = Closure::wrap(Box::new(callback) as Box<dyn FnMut(web_sys::Event)>);
+        target
+            .add_event_listener_with_callback(event, callback.as_ref().unchecked_ref())
+            .map_err(js_to_error)?;
+        self.0.push(Listener {
+            target: target.clone(),
+            event,
+            callback,
+        });
+        Ok(())
+    }
+
+    fn add_progress(
+        &mut self,
+        target: &EventTarget,
+        callback: ProgressCallback,
+    ) -> Result<(), Error> {
+        self.add(target, "progress", move |event| {
+            let event: ProgressEvent = event.unchecked_into();
+            let progress = Progress {
+                loaded: event.loaded() as u64,
+                total: event.length_computable().then(|| event.total() as u64),
+            };
+            (callback.borrow_mut())(progress);
+        })
+    }
+}
+
+impl Drop for Listeners {
+    fn drop(&mut self) {
+        for listener in &self.0 {
+            let _ = listener.target.remove_event_listener_with_callback(
+                listener.event,
+                listener.callback.as_ref().unchecked_ref(),
+            );
+        }
+    }
+}
+
+/// Aborts the request unless it is disarmed, i.e. when the future sending it is dropped early.
+struct AbortOnDrop(Option<XmlHttpRequest>);
+
+impl AbortOnDrop {
+    fn disarm(mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        if let Some(xhr) = &self.0 {
+            let _ = xhr.abort();
+        }
+    }
+}
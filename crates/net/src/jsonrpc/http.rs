@@ -0,0 +1,247 @@
+use super::{request, Response};
+#[cfg(all(feature = "native", not(target_arch = "wasm32")))]
+use crate::http::native::Request;
+#[cfg(not(all(feature = "native", not(target_arch = "wasm32"))))]
+use crate::http::Request;
+use crate::Error;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// A JSON-RPC client which sends every call as a `POST` request to one URL.
+///
+/// # Example
+///
+/// ```
+/// # use gloo_net::jsonrpc::HttpClient;
+/// # async fn no_run() -> Result<(), gloo_net::Error> {
+/// let client = HttpClient::new("/rpc").header("Authorization", "Bearer token");
+///
+/// let mut batch = client.batch();
+/// let sum = batch.call("add", &[1, 2])?;
+/// let user = batch.call("user.get", &serde_json::json!({ "id": 7 }))?;
+/// batch.notify("log", &["batch sent"])?;
+/// let responses = batch.send().await?;
+///
+/// let sum: i32 = responses.get(sum)?;
+/// let user: serde_json::Value = responses.get(user)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct HttpClient {
+    url: String,
+    headers: Vec<(String, String)>,
+    timeout: Option<Duration>,
+    next_id: Cell<u64>,
+}
+
+impl HttpClient {
+    /// Creates a client which sends calls to `url`.
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            headers: Vec::new(),
+            timeout: None,
+            next_id: Cell::new(1),
+        }
+    }
+
+    /// Sets a header on every request.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Fails requests with [`Error::Timeout`] if no response has been received after `timeout`.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Calls `method` and converts its result to `R`.
+    ///
+    /// `params` has to serialize to an array or an object, or to `null` for no parameters.
+    pub async fn call<P, R>(&self, method: &str, params: &P) -> Result<R, Error>
+    where
+        P: Serialize + ?Sized,
+        R: DeserializeOwned,
+    {
+        let id = self.next_id();
+        let response = self
+            .post(&request(method, params, Some(id))?)
+            .await?
+            .ok_or_else(|| self.invalid("an empty response"))?;
+        match Response::parse(&response) {
+            Some(response) if response.id == Some(id) || response.id.is_none() => {
+                response.decode(&self.url)
+            }
+            _ => Err(self.invalid("a response which does not match the call")),
+        }
+    }
+
+    /// Sends a notification, for which the server does not respond.
+    pub async fn notify<P: Serialize + ?Sized>(
+        &self,
+        method: &str,
+        params: &P,
+    ) -> Result<(), Error> {
+        self.post(&request(method, params, None)?).await?;
+        Ok(())
+    }
+
+    /// Starts a batch of calls and notifications, which are sent in a single request.
+    pub fn batch(&self) -> Batch<'_> {
+        Batch {
+            client: self,
+            requests: Vec::new(),
+        }
+    }
+
+    fn next_id(&self) -> u64 {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        id
+    }
+
+    /// Posts `body`, returning the JSON response, or `None` if the response was empty.
+    async fn post(&self, body: &Value) -> Result<Option<Value>, Error> {
+        let mut builder = Request::post(self.url.as_str()).header("Accept", "application/json");
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        let response = builder.json(body)?.send().await?;
+
+        // Servers may report errors with an unsuccessful status and a JSON-RPC error object.
+        let status_error = (!response.ok()).then(|| Error::Status {
+            url: response.url(),
+            status: response.status(),
+            status_text: response.status_text(),
+        });
+        let text = response.text().await?;
+        if text.trim().is_empty() {
+            return match status_error {
+                Some(error) => Err(error),
+                None => Ok(None),
+            };
+        }
+        match serde_json::from_str(&text) {
+            Ok(value) => Ok(Some(value)),
+            Err(error) => Err(status_error.unwrap_or_else(|| Error::Decode {
+                url: response.url(),
+                source: Box::new(error),
+            })),
+        }
+    }
+
+    fn invalid(&self, what: &str) -> Error {
+        Error::Decode {
+            url: self.url.clone(),
+            source: format!("the server sent {what}").into(),
+        }
+    }
+}
+
+/// Calls and notifications sent together in a single request, created by
+/// [`HttpClient::batch`].
+#[derive(Debug)]
+pub struct Batch<'a> {
+    client: &'a HttpClient,
+    requests: Vec<Value>,
+}
+
+/// Identifies the result of a call in a [`BatchResponse`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BatchId(u64);
+
+impl Batch<'_> {
+    /// Adds a call of `method`, whose result can be taken from the [`BatchResponse`] with the
+    /// returned id.
+    pub fn call<P: Serialize + ?Sized>(
+        &mut self,
+        method: &str,
+        params: &P,
+    ) -> Result<BatchId, Error> {
+        let id = self.client.next_id();
+        self.requests.push(request(method, params, Some(id))?);
+        Ok(BatchId(id))
+    }
+
+    /// Adds a notification.
+    pub fn notify<P: Serialize + ?Sized>(&mut self, method: &str, params: &P) -> Result<(), Error> {
+        self.requests.push(request(method, params, None)?);
+        Ok(())
+    }
+
+    /// Sends the batch.
+    ///
+    /// An empty batch is not sent.
+    pub async fn send(self) -> Result<BatchResponse, Error> {
+        let mut results = HashMap::new();
+        if self.requests.is_empty() {
+            return Ok(BatchResponse {
+                url: self.client.url.clone(),
+                results,
+            });
+        }
+        let mut errors = Vec::new();
+        let responses = match self.client.post(&Value::Array(self.requests)).await? {
+            Some(Value::Array(responses)) => responses,
+            // A batch with only notifications has no response.
+            None => Vec::new(),
+            // The server failed to handle the batch as a whole.
+            Some(response) => vec![response],
+        };
+        for response in responses.iter().filter_map(Response::parse) {
+            match response.id {
+                Some(id) => {
+                    results.insert(id, response.result);
+                }
+                None => errors.extend(response.result.err()),
+            }
+        }
+        if results.is_empty() {
+            if let Some(error) = errors.pop() {
+                return Err(error.into());
+            }
+        }
+        Ok(BatchResponse {
+            url: self.client.url.clone(),
+            results,
+        })
+    }
+}
+
+/// The results of the calls in a [`Batch`].
+#[derive(Debug)]
+pub struct BatchResponse {
+    url: String,
+    results: HashMap<u64, Result<Value, super::RpcError>>,
+}
+
+impl BatchResponse {
+    /// Converts the result of the call `id` to `R`.
+    ///
+    /// Fails with [`Error::Decode`] if the server did not respond to the call.
+    pub fn get<R: DeserializeOwned>(&self, id: BatchId) -> Result<R, Error> {
+        let result = self
+            .results
+            .get(&id.0)
+            .cloned()
+            .ok_or_else(|| Error::Decode {
+                url: self.url.clone(),
+                source: format!("the server did not respond to call {}", id.0).into(),
+            })?;
+        Response {
+            id: Some(id.0),
+            result,
+        }
+        .decode(&self.url)
+    }
+}
@@ -0,0 +1,153 @@
+//! A [JSON-RPC 2.0](https://www.jsonrpc.org/specification) client.
+//!
+//! This API is provided over the following transports:
+//! - [HTTP][HttpClient], with the `http` feature: every call is a `POST` request, and several
+//!   calls can be sent in one [`Batch`].
+//! - [WebSocket][WebSocketClient], with the `websocket` feature: calls are matched to their
+//!   responses by id, and the server can send [`Notification`]s.
+//!
+//! Parameters and results are converted with `serde`. Errors returned by the server are reported
+//! as [`Error::JsonRpc`](crate::Error::JsonRpc).
+//!
+//! # Example
+//!
+//! ```
+//! # use gloo_net::jsonrpc::HttpClient;
+//! # async fn no_run() -> Result<(), gloo_net::Error> {
+//! let client = HttpClient::new("/rpc");
+//! let sum: i32 = client.call("add", &[1, 2]).await?;
+//! assert_eq!(sum, 3);
+//! # Ok(())
+//! # }
+//! ```
+
+// The shared parts are unused without a transport.
+#![cfg_attr(not(any(feature = "http", feature = "websocket")), allow(dead_code))]
+
+#[cfg(feature = "http")]
+mod http;
+#[cfg(feature = "websocket")]
+mod websocket;
+
+#[cfg(feature = "http")]
+pub use self::http::{Batch, BatchId, BatchResponse, HttpClient};
+#[cfg(feature = "websocket")]
+pub use self::websocket::{Notifications, WebSocketClient};
+
+use crate::Error;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::fmt;
+
+/// An error object returned by the server in place of a result.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("JSON-RPC error {code}: {message}")]
+pub struct RpcError {
+    /// The error code. Codes from `-32768` to `-32000` are reserved by the specification.
+    pub code: i64,
+    /// A short description of the error.
+    pub message: String,
+    /// Additional information about the error, if the server sent any.
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    /// The request was not a valid JSON-RPC request.
+    pub const INVALID_REQUEST: i64 = -32600;
+    /// The method does not exist.
+    pub const METHOD_NOT_FOUND: i64 = -32601;
+    /// The parameters were invalid for the method.
+    pub const INVALID_PARAMS: i64 = -32602;
+    /// An internal error of the server.
+    pub const INTERNAL_ERROR: i64 = -32603;
+    /// The server could not parse the request.
+    pub const PARSE_ERROR: i64 = -32700;
+
+    fn from_value(error: &Value) -> Self {
+        Self {
+            code: error["code"].as_i64().unwrap_or(Self::INTERNAL_ERROR),
+            message: error["message"].as_str().unwrap_or_default().to_string(),
+            data: error.get("data").cloned(),
+        }
+    }
+}
+
+/// A notification sent by the server, which does not expect a response.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    /// The name of the method.
+    pub method: String,
+    /// The parameters, `null` if there are none.
+    pub params: Value,
+    /// The URL of the WebSocket connection it was received over.
+    pub url: String,
+}
+
+impl Notification {
+    /// Converts the parameters to `T`.
+    ///
+    /// Fails with [`Error::Decode`] if they do not match `T`.
+    pub fn params<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        T::deserialize(&self.params).map_err(|e| Error::Decode {
+            url: self.url.clone(),
+            source: Box::new(e),
+        })
+    }
+}
+
+impl fmt::Display for Notification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({})", self.method, self.params)
+    }
+}
+
+/// Builds a request object. Notifications have no `id`.
+fn request<P: Serialize + ?Sized>(
+    method: &str,
+    params: &P,
+    id: Option<u64>,
+) -> Result<Value, Error> {
+    let params = serde_json::to_value(params).map_err(|e| Error::Encode(Box::new(e)))?;
+    let mut request = json!({ "jsonrpc": "2.0", "method": method });
+    // `()` and `None` serialize to `null`, which means no parameters.
+    if !params.is_null() {
+        request["params"] = params;
+    }
+    if let Some(id) = id {
+        request["id"] = id.into();
+    }
+    Ok(request)
+}
+
+/// A response object, matched to its request by `id`.
+struct Response {
+    /// `None` if the server could not determine the id, e.g. because the request was invalid.
+    id: Option<u64>,
+    result: Result<Value, RpcError>,
+}
+
+impl Response {
+    /// Parses a response object, returning `None` if `value` is not one.
+    fn parse(value: &Value) -> Option<Self> {
+        let id = match value.get("id")? {
+            Value::Null => None,
+            id => Some(id.as_u64()?),
+        };
+        let result = match (value.get("result"), value.get("error")) {
+            (Some(result), None) => Ok(result.clone()),
+            (_, Some(error)) => Err(RpcError::from_value(error)),
+            (None, None) => return None,
+        };
+        Some(Self { id, result })
+    }
+
+    /// Converts the result to `R`. `url` is where the response came from.
+    fn decode<R: DeserializeOwned>(self, url: &str) -> Result<R, Error> {
+        let result = self.result?;
+        R::deserialize(result).map_err(|e| Error::Decode {
+            url: url.to_string(),
+            source: Box::new(e),
+        })
+    }
+}
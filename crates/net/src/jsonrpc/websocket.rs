@@ -0,0 +1,335 @@
+use super::{request, Notification, Response};
+use crate::websocket::futures::WebSocket;
+use crate::websocket::{Message, WebSocketError};
+use crate::Error;
+use futures_channel::{mpsc, oneshot};
+use futures_core::Stream;
+use futures_sink::Sink;
+use gloo_timers::future::TimeoutFuture;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+use std::future::{poll_fn, Future};
+use std::pin::Pin;
+use std::rc::{Rc, Weak};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// A JSON-RPC client which sends calls over a [`WebSocket`].
+///
+/// Responses are matched to their calls by id, so several calls can be in flight at once.
+/// Notifications sent by the server are delivered to every [`Notifications`] stream. Requests
+/// sent by the server are ignored.
+///
+/// Once the connection closes, pending and subsequent calls fail with
+/// [`Error::ConnectionClosed`] and the notification streams end. Dropping the client closes the
+/// connection.
+///
+/// # Example
+///
+/// ```
+/// # use gloo_net::jsonrpc::WebSocketClient;
+/// # use gloo_net::websocket::futures::WebSocket;
+/// # use futures::StreamExt;
+/// # use std::time::Duration;
+/// # async fn no_run() -> Result<(), gloo_net::Error> {
+/// let ws = WebSocket::open("wss://example.com/rpc").unwrap();
+/// let client = WebSocketClient::new(ws).timeout(Duration::from_secs(10));
+///
+/// let mut notifications = client.notifications();
+/// let subscription: u64 = client.call("subscribe", &["prices"]).await?;
+/// while let Some(notification) = notifications.next().await {
+///     let price: f64 = notification.params()?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct WebSocketClient {
+    inner: Rc<Inner>,
+}
+
+struct Inner {
+    ws: RefCell<WebSocket>,
+    url: String,
+    next_id: Cell<u64>,
+    timeout: Cell<Option<Duration>>,
+    closed: Cell<bool>,
+    pending: RefCell<HashMap<u64, oneshot::Sender<Response>>>,
+    subscribers: RefCell<Vec<mpsc::UnboundedSender<Notification>>>,
+}
+
+impl WebSocketClient {
+    /// Creates a client which sends calls over `ws`, which may still be connecting.
+    ///
+    /// The client reads all messages of `ws` from now on.
+    pub fn new(ws: WebSocket) -> Self {
+        let inner = Rc::new(Inner {
+            url: ws.url(),
+            ws: RefCell::new(ws),
+            next_id: Cell::new(1),
+            timeout: Cell::new(None),
+            closed: Cell::new(false),
+            pending: RefCell::new(HashMap::new()),
+            subscribers: RefCell::new(Vec::new()),
+        });
+        wasm_bindgen_futures::spawn_local(receive(Rc::downgrade(&inner)));
+        Self { inner }
+    }
+
+    /// Fails calls with [`Error::Timeout`] if no response has been received after `timeout`.
+    pub fn timeout(self, timeout: Duration) -> Self {
+        self.inner.timeout.set(Some(timeout));
+        self
+    }
+
+    /// Calls `method` and converts its result to `R`.
+    ///
+    /// `params` has to serialize to an array or an object, or to `null` for no parameters.
+    pub async fn call<P, R>(&self, method: &str, params: &P) -> Result<R, Error>
+    where
+        P: Serialize + ?Sized,
+        R: DeserializeOwned,
+    {
+        let id = self.inner.next_id.get();
+        self.inner.next_id.set(id + 1);
+        let message = request(method, params, Some(id))?;
+
+        let (sender, mut receiver) = oneshot::channel();
+        self.inner.pending.borrow_mut().insert(id, sender);
+        // Forgets the call when it completes, times out or is dropped.
+        let _call = Call {
+            inner: &self.inner,
+            id,
+        };
+        self.send(&message).await?;
+
+        let response = match self.inner.timeout.get() {
+            Some(timeout) => {
+                let millis = timeout.as_millis().min(i32::MAX as u128) as u32;
+                let mut timer = TimeoutFuture::new(millis);
+                poll_fn(|cx| {
+                    if let Poll::Ready(response) = Pin::new(&mut receiver).poll(cx) {
+                        return Poll::Ready(response);
+                    }
+                    Pin::new(&mut timer)
+                        .poll(cx)
+                        .map(|()| Err(oneshot::Canceled))
+                })
+                .await
+                .map_err(|_| self.timed_out_or_closed(timeout))?
+            }
+            None => receiver.await.map_err(|_| closed(&self.inner.url))?,
+        };
+        response.decode(&self.inner.url)
+    }
+
+    /// Sends a notification, for which the server does not respond.
+    pub async fn notify<P: Serialize + ?Sized>(
+        &self,
+        method: &str,
+        params: &P,
+    ) -> Result<(), Error> {
+        self.send(&request(method, params, None)?).await
+    }
+
+    /// A stream of the notifications sent by the server from now on.
+    pub fn notifications(&self) -> Notifications {
+        let (sender, receiver) = mpsc::unbounded();
+        if !self.inner.closed.get() {
+            self.inner.subscribers.borrow_mut().push(sender);
+        }
+        Notifications { receiver }
+    }
+
+    async fn send(&self, message: &Value) -> Result<(), Error> {
+        let url = &self.inner.url;
+        if self.inner.closed.get() {
+            return Err(closed(url));
+        }
+        let ws = &self.inner.ws;
+        poll_fn(|cx| Pin::new(&mut *ws.borrow_mut()).poll_ready(cx))
+            .await
+            .map_err(|error| websocket_error(url, error))?;
+        Pin::new(&mut *ws.borrow_mut())
+            .start_send(Message::Text(message.to_string()))
+            .map_err(|error| websocket_error(url, error))
+    }
+
+    fn timed_out_or_closed(&self, timeout: Duration) -> Error {
+        if self.inner.closed.get() {
+            closed(&self.inner.url)
+        } else {
+            Error::Timeout(timeout)
+        }
+    }
+}
+
+impl fmt::Debug for WebSocketClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebSocketClient")
+            .field("url", &self.inner.url)
+            .field("timeout", &self.inner.timeout.get())
+            .field("closed", &self.inner.closed.get())
+            .field("pending", &self.inner.pending.borrow().len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Inner {
+    /// Hands the responses in `text` to their calls, and the notifications to the subscribers.
+    fn dispatch(&self, text: &str) {
+        let messages = match serde_json::from_str(text) {
+            Ok(Value::Array(messages)) => messages,
+            Ok(message) => vec![message],
+            Err(_) => return,
+        };
+        for message in messages {
+            if let Some(response) = Response::parse(&message) {
+                let call = response
+                    .id
+                    .and_then(|id| self.pending.borrow_mut().remove(&id));
+                if let Some(call) = call {
+                    let _ = call.send(response);
+                }
+                continue;
+            }
+            if let (Some(method), None) = (message["method"].as_str(), message.get("id")) {
+                let notification = Notification {
+                    method: method.to_string(),
+                    params: message.get("params").cloned().unwrap_or(Value::Null),
+                    url: self.url.clone(),
+                };
+                self.subscribers
+                    .borrow_mut()
+                    .retain(|subscriber| subscriber.unbounded_send(notification.clone()).is_ok());
+            }
+        }
+    }
+
+    /// Fails the pending calls and ends the notification streams.
+    fn close(&self) {
+        self.closed.set(true);
+        let pending = std::mem::take(&mut *self.pending.borrow_mut());
+        drop(pending);
+        self.subscribers.borrow_mut().clear();
+    }
+}
+
+/// Reads the messages of the WebSocket until it closes or the client is dropped.
+async fn receive(inner: Weak<Inner>) {
+    loop {
+        let message = poll_fn(|cx| match inner.upgrade() {
+            Some(inner) => Pin::new(&mut *inner.ws.borrow_mut()).poll_next(cx),
+            None => Poll::Ready(None),
+        })
+        .await;
+        let inner = match inner.upgrade() {
+            Some(inner) => inner,
+            None => return,
+        };
+        match message {
+            Some(Ok(Message::Text(text))) => inner.dispatch(&text),
+            Some(Ok(Message::Bytes(bytes))) => {
+                if let Ok(text) = std::str::from_utf8(&bytes) {
+                    inner.dispatch(text);
+                }
+            }
+            Some(Err(_)) | None => {
+                inner.close();
+                return;
+            }
+        }
+    }
+}
+
+/// A call waiting for its response.
+struct Call<'a> {
+    inner: &'a Inner,
+    id: u64,
+}
+
+impl Drop for Call<'_> {
+    fn drop(&mut self) {
+        self.inner.pending.borrow_mut().remove(&self.id);
+    }
+}
+
+/// The notifications sent by the server, created by [`WebSocketClient::notifications`].
+///
+/// The stream ends when the connection closes.
+#[derive(Debug)]
+pub struct Notifications {
+    receiver: mpsc::UnboundedReceiver<Notification>,
+}
+
+impl Stream for Notifications {
+    type Item = Notification;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Notification>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+fn websocket_error(url: &str, error: WebSocketError) -> Error {
+    match error {
+        WebSocketError::MessageSendError(error) => Error::JsError(error),
+        error => Error::ConnectionClosed {
+            url: url.to_string(),
+            source: Some(error),
+        },
+    }
+}
+
+fn closed(url: &str) -> Error {
+    Error::ConnectionClosed {
+        url: url.to_string(),
+        source: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    async fn websocket_client_works() {
+        let ws_echo_server_url =
+            option_env!("WS_ECHO_SERVER_URL").expect("Did you set WS_ECHO_SERVER_URL?");
+
+        let ws = WebSocket::open(ws_echo_server_url).unwrap();
+        let client = WebSocketClient::new(ws).timeout(Duration::from_millis(500));
+        let mut notifications = client.notifications();
+
+        // the echo-server sends notifications back as they are
+        client.notify("greet", &["hello"]).await.unwrap();
+        let notification = notifications.next().await.unwrap();
+        assert_eq!(notification.method, "greet");
+        assert_eq!(
+            notification.params::<Vec<String>>().unwrap(),
+            vec!["hello".to_string()]
+        );
+        assert!(matches!(
+            notification.params::<u32>(),
+            Err(Error::Decode { url, .. }) if url == client.inner.url
+        ));
+
+        // but calls never get a response
+        let result = client.call::<_, u32>("greet", &["hello"]).await;
+        assert!(matches!(result, Err(Error::Timeout(_))));
+
+        client.inner.close();
+        let result = client.notify("greet", &["hello"]).await;
+        assert!(matches!(
+            result,
+            Err(Error::ConnectionClosed { source: None, .. })
+        ));
+        assert!(notifications.next().await.is_none());
+    }
+}
@@ -130,10 +130,18 @@ impl WebSocket {
         ws.set_binary_type(BinaryType::Arraybuffer);
 
         let (sender, receiver) = mpsc::unbounded();
+        #[cfg(feature = "tracing")]
+        let url = ws.url();
+        #[cfg(feature = "tracing")]
+        tracing::debug!(%url, "WebSocket connecting");
 
         let open_callback: Closure<dyn FnMut()> = {
             let waker = Rc::clone(&waker);
+            #[cfg(feature = "tracing")]
+            let url = url.clone();
             Closure::wrap(Box::new(move || {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(%url, "WebSocket opened");
                 if let Some(waker) = waker.borrow_mut().take() {
                     waker.wake();
                 }
@@ -161,7 +169,11 @@ impl WebSocket {
         let error_callback: Closure<dyn FnMut(web_sys::Event)> = {
             let sender = sender.clone();
             let waker = Rc::clone(&waker);
+            #[cfg(feature = "tracing")]
+            let url = url.clone();
             Closure::wrap(Box::new(move |_e: web_sys::Event| {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(%url, "WebSocket error");
                 if let Some(waker) = waker.borrow_mut().take() {
                     waker.wake();
                 }
@@ -179,6 +191,14 @@ impl WebSocket {
                     reason: e.reason(),
                     was_clean: e.was_clean(),
                 };
+                #[cfg(feature = "tracing")]
+                tracing::debug!(
+                    %url,
+                    code = close_event.code,
+                    reason = %close_event.reason,
+                    was_clean = close_event.was_clean,
+                    "WebSocket closed"
+                );
                 let _ = sender.unbounded_send(StreamMessage::CloseEvent(close_event));
                 let _ = sender.unbounded_send(StreamMessage::ConnectionClose);
             }) as Box<dyn FnMut(web_sys::CloseEvent)>)
@@ -234,6 +254,11 @@ impl WebSocket {
         }
     }
 
+    /// The URL the websocket connects to.
+    pub fn url(&self) -> String {
+        self.ws.url()
+    }
+
     /// The extensions in use.
     pub fn extensions(&self) -> String {
         self.ws.extensions()
@@ -1,6 +1,15 @@
-use gloo_net::http::Request;
+// The tests predate `Request::get` and friends taking any `impl Into<String>`.
+#![allow(clippy::needless_borrows_for_generic_args)]
+use futures::StreamExt;
+use gloo_net::http::{
+    CachePolicy, Client, ConcurrencyLimit, Deduplicate, Headers, Interceptor, Json, Method,
+    MockTransport, Multipart, Next, Request, Response, ResponseCache, RetryPolicy, SendFuture,
+    Transport, Url,
+};
+use gloo_net::Error;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 use wasm_bindgen_test::*;
 
 wasm_bindgen_test_configure!(run_in_browser);
@@ -17,6 +26,18 @@ async fn fetch() {
     assert_eq!(resp.status(), 200);
 }
 
+#[wasm_bindgen_test]
+async fn fetch_unavailable() {
+    let global = js_sys::global();
+    let key = wasm_bindgen::JsValue::from_str("fetch");
+    let fetch = js_sys::Reflect::get(&global, &key).unwrap();
+    js_sys::Reflect::set(&global, &key, &wasm_bindgen::JsValue::UNDEFINED).unwrap();
+
+    let result = Request::get(&format!("{}/get", *HTTPBIN_URL)).send().await;
+    js_sys::Reflect::set(&global, &key, &fetch).unwrap();
+    assert!(matches!(result, Err(Error::FetchUnavailable)));
+}
+
 #[wasm_bindgen_test]
 async fn fetch_json() {
     #[derive(Deserialize, Debug)]
@@ -42,6 +63,97 @@ async fn auth_valid_bearer() {
     assert_eq!(resp.status(), 200);
 }
 
+#[wasm_bindgen_test]
+async fn error_for_status() {
+    let url = format!("{}/status/404", *HTTPBIN_URL);
+    let result = Request::get(&url).send().await.unwrap().error_for_status();
+    assert!(matches!(result, Err(Error::Status { status: 404, .. })));
+
+    let resp = Request::get(&format!("{}/get", *HTTPBIN_URL))
+        .send()
+        .await
+        .unwrap()
+        .error_for_status()
+        .unwrap();
+    resp.text().await.unwrap();
+    assert!(matches!(resp.text().await, Err(Error::BodyUsed { .. })));
+}
+
+#[wasm_bindgen_test]
+async fn http_conversions() {
+    #[derive(Deserialize, Debug)]
+    struct HttpBin {
+        headers: std::collections::HashMap<String, String>,
+        data: String,
+    }
+
+    let request = http::Request::post(format!("{}/post", *HTTPBIN_URL))
+        .header("X-Token", "secret")
+        .body("hello")
+        .unwrap();
+    let response = Request::try_from(request)
+        .unwrap()
+        .send()
+        .await
+        .unwrap()
+        .into_http()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), http::StatusCode::OK);
+
+    let body: HttpBin = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(body.headers["X-Token"], "secret");
+    assert_eq!(body.data, "hello");
+
+    let response = http::Response::builder()
+        .status(201)
+        .header("Content-Type", "text/plain")
+        .body("created")
+        .unwrap();
+    let response = Response::try_from(response).unwrap();
+    assert_eq!(response.status(), 201);
+    assert_eq!(response.status_text(), "Created");
+    assert_eq!(response.text().await.unwrap(), "created");
+
+    let mut map = http::HeaderMap::new();
+    map.insert(
+        "x-latin1",
+        http::HeaderValue::from_bytes(b"caf\xe9").unwrap(),
+    );
+    assert!(matches!(
+        Headers::try_from(&map),
+        Err(Error::InvalidHeader { name, .. }) if name == "x-latin1"
+    ));
+}
+
+#[wasm_bindgen_test]
+async fn body_codecs() {
+    #[derive(Deserialize, Debug)]
+    struct HttpBin {
+        headers: std::collections::HashMap<String, String>,
+        json: Vec<u32>,
+    }
+
+    let resp = Request::post(&format!("{}/post", *HTTPBIN_URL))
+        .accept(Json)
+        .encode(Json, &[1, 2, 3])
+        .unwrap()
+        .send()
+        .await
+        .unwrap();
+    let body: HttpBin = resp.decode_negotiated().await.unwrap();
+    assert_eq!(body.headers["Accept"], "application/json");
+    assert_eq!(body.headers["Content-Type"], "application/json");
+    assert_eq!(body.json, [1, 2, 3]);
+
+    let resp = Response::builder()
+        .header("Content-Type", "text/plain")
+        .body(Some("text"))
+        .unwrap();
+    let result = resp.decode_negotiated::<String>().await;
+    assert!(matches!(result, Err(Error::Decode { .. })));
+}
+
 #[wasm_bindgen_test]
 async fn gzip_response() {
     #[derive(Deserialize, Debug)]
@@ -128,6 +240,35 @@ async fn query_preserve_initial() {
     assert_eq!(resp.url(), format!("{}/get?key=value&q=val", *HTTPBIN_URL));
 }
 
+#[wasm_bindgen_test]
+async fn serialize_query() {
+    #[derive(Serialize)]
+    struct Search {
+        q: &'static str,
+        tags: Vec<&'static str>,
+        page: Option<u32>,
+    }
+
+    let url = Url::new(*HTTPBIN_URL)
+        .join("anything")
+        .segment("a b")
+        .unwrap();
+    let resp = Request::get(&url)
+        .serialize_query(&Search {
+            q: "a&b",
+            tags: vec!["x", "y"],
+            page: None,
+        })
+        .unwrap()
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        resp.url(),
+        format!("{}/anything/a%20b?q=a%26b&tags=x&tags=y", *HTTPBIN_URL)
+    );
+}
+
 #[wasm_bindgen_test]
 async fn query_preserve_duplicate_params() {
     let resp = Request::get(&format!("{}/get", *HTTPBIN_URL))
@@ -137,3 +278,611 @@ async fn query_preserve_duplicate_params() {
         .unwrap();
     assert_eq!(resp.url(), format!("{}/get?q=1&q=2", *HTTPBIN_URL));
 }
+
+#[wasm_bindgen_test]
+async fn request_timeout() {
+    let result = Request::get(&format!("{}/delay/3", *HTTPBIN_URL))
+        .timeout(Duration::from_millis(500))
+        .send()
+        .await;
+    assert!(matches!(result, Err(Error::Timeout(_))));
+}
+
+#[wasm_bindgen_test]
+async fn retry_gives_up_after_max_attempts() {
+    let policy = RetryPolicy::new()
+        .max_attempts(2)
+        .backoff(Duration::from_millis(10), Duration::from_millis(10));
+    let resp = Request::get(&format!("{}/status/503", *HTTPBIN_URL))
+        .retry(policy)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 503);
+}
+
+#[wasm_bindgen_test]
+async fn stream_body() {
+    let resp = Request::get(&format!(
+        "{}/stream-bytes/4096?chunk_size=1024",
+        *HTTPBIN_URL
+    ))
+    .send()
+    .await
+    .unwrap();
+    let chunks: Vec<_> = resp.bytes_stream().collect().await;
+    let len: usize = chunks.into_iter().map(|chunk| chunk.unwrap().len()).sum();
+    assert_eq!(len, 4096);
+}
+
+#[wasm_bindgen_test]
+async fn json_lines() {
+    #[derive(Deserialize, Debug)]
+    struct HttpBin {
+        id: u32,
+    }
+
+    let resp = Request::get(&format!("{}/stream/3", *HTTPBIN_URL))
+        .send()
+        .await
+        .unwrap();
+    let ids: Vec<u32> = resp
+        .json_lines::<HttpBin>()
+        .map(|record| record.unwrap().id)
+        .collect()
+        .await;
+    assert_eq!(ids, [0, 1, 2]);
+
+    let resp = Response::builder()
+        .body(Some("{\"id\":1}\n\n{\"id\":2}\r\nnot json\n{\"id\":3}"))
+        .unwrap();
+    let records: Vec<_> = resp.json_lines::<HttpBin>().collect().await;
+    assert_eq!(records.len(), 4);
+    assert_eq!(records[1].as_ref().unwrap().id, 2);
+    assert!(matches!(records[2], Err(Error::Decode { .. })));
+    assert_eq!(records[3].as_ref().unwrap().id, 3);
+}
+
+#[wasm_bindgen_test]
+async fn stream_body_progress() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let resp = Request::get(&format!("{}/bytes/2048", *HTTPBIN_URL))
+        .send()
+        .await
+        .unwrap();
+    let reports = Rc::new(RefCell::new(Vec::new()));
+    let stream = resp.bytes_stream_with_progress({
+        let reports = reports.clone();
+        move |progress| reports.borrow_mut().push(progress)
+    });
+    let _: Vec<_> = stream.collect().await;
+    let last = *reports.borrow().last().unwrap();
+    assert_eq!(last.loaded, 2048);
+    assert_eq!(last.total, Some(2048));
+    assert_eq!(last.fraction(), Some(1.0));
+}
+
+#[wasm_bindgen_test]
+async fn xhr_upload_progress() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let uploaded = Rc::new(Cell::new(0));
+    let resp = Request::post(&format!("{}/post", *HTTPBIN_URL))
+        .body("x".repeat(4096))
+        .unwrap()
+        .xhr()
+        .on_upload_progress({
+            let uploaded = uploaded.clone();
+            move |progress| uploaded.set(progress.loaded)
+        })
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.url(), format!("{}/post", *HTTPBIN_URL));
+    assert_eq!(uploaded.get(), 4096);
+}
+
+#[wasm_bindgen_test]
+async fn stream_request_body() {
+    #[derive(Deserialize, Debug)]
+    struct HttpBin {
+        data: String,
+    }
+
+    // Streaming uploads with `fetch` need HTTP/2, so the stream is sent with `XMLHttpRequest`.
+    let chunks = futures::stream::iter(vec![b"hello ".to_vec(), b"world".to_vec()]);
+    let resp = Request::post(&format!("{}/anything", *HTTPBIN_URL))
+        .body_stream(chunks)
+        .unwrap()
+        .xhr()
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let resp: HttpBin = resp.json().await.unwrap();
+    assert_eq!(resp.data, "hello world");
+}
+
+#[wasm_bindgen_test]
+async fn post_multipart() {
+    #[derive(Deserialize, Debug)]
+    struct Form {
+        name: String,
+    }
+
+    #[derive(Deserialize, Debug)]
+    struct Files {
+        upload: String,
+    }
+
+    #[derive(Deserialize, Debug)]
+    struct HttpBin {
+        form: Form,
+        files: Files,
+    }
+
+    let file = gloo_file::File::new("hello.txt", "hello world");
+    let form = Multipart::new().text("name", "ferris").file(
+        "upload",
+        &file,
+        "hello.txt",
+        Some("text/plain"),
+    );
+    let part = form.get("name").unwrap();
+    assert!(matches!(
+        part.json::<String>().await,
+        Err(Error::Decode { .. })
+    ));
+    let resp = Request::post(&format!("{}/post", *HTTPBIN_URL))
+        .multipart(form)
+        .unwrap()
+        .send()
+        .await
+        .unwrap();
+    let json: HttpBin = resp.json().await.unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(json.form.name, "ferris");
+    assert_eq!(json.files.upload, "hello world");
+}
+
+#[wasm_bindgen_test]
+async fn client_defaults_and_interceptors() {
+    #[derive(Deserialize, Debug)]
+    struct HttpBin {
+        headers: std::collections::HashMap<String, String>,
+    }
+
+    struct Tag;
+
+    impl Interceptor for Tag {
+        fn intercept<'a>(&'a self, request: Request, next: Next<'a>) -> SendFuture<'a> {
+            request.headers().set("X-Tag", "intercepted");
+            next.run(request)
+        }
+    }
+
+    let client = Client::builder()
+        .base_url(&HTTPBIN_URL)
+        .header("X-Default", "yes")
+        .interceptor(Tag)
+        .build();
+    let resp = client.get("/headers").send().await.unwrap();
+    let json: HttpBin = resp.json().await.unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(json.headers["X-Default"], "yes");
+    assert_eq!(json.headers["X-Tag"], "intercepted");
+
+    let client = Client::builder()
+        .header("Authorization", "Bearer secret")
+        .build();
+    let debug = format!("{:?}", client);
+    assert!(debug.contains("Authorization"));
+    assert!(!debug.contains("secret"));
+}
+
+#[wasm_bindgen_test]
+async fn mock_transport() {
+    let mock = MockTransport::new();
+    mock.on(Method::GET, "/users/1")
+        .header("Authorization", "Bearer token")
+        .respond(
+            Response::builder()
+                .status(201)
+                .body(Some("ferris"))
+                .unwrap(),
+        );
+
+    let client = Client::builder()
+        .base_url("https://api.example.com")
+        .transport(mock.clone())
+        .build();
+    let resp = client
+        .get("/users/1")
+        .header("Authorization", "Bearer token")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 201);
+    assert_eq!(resp.text().await.unwrap(), "ferris");
+
+    let unmatched = client.get("/users/2").send().await;
+    assert!(matches!(unmatched, Err(Error::GlooError(_))));
+
+    mock.assert_requested(Method::GET, "https://api.example.com/users/1");
+    assert_eq!(mock.request_count(), 2);
+}
+
+#[wasm_bindgen_test]
+async fn response_cache() {
+    let mock = MockTransport::new();
+    mock.on(Method::GET, "/data").respond(
+        Response::builder()
+            .header("Cache-Control", "max-age=60")
+            .header("ETag", "\"v1\"")
+            .body(Some("cached"))
+            .unwrap(),
+    );
+
+    let cache = ResponseCache::new("gloo-net-test-cache-first", CachePolicy::CacheFirst);
+    cache.clear().await.unwrap();
+    let client = Client::builder()
+        .base_url("https://api.example.com")
+        .interceptor(cache)
+        .transport(mock.clone())
+        .build();
+    for _ in 0..2 {
+        let resp = client.get("/data").send().await.unwrap();
+        assert!(!resp.headers().has("X-Gloo-Cache-Stored-At"));
+        assert_eq!(resp.text().await.unwrap(), "cached");
+    }
+    assert_eq!(mock.request_count(), 1);
+    let resp = client.get("/data").send().await.unwrap();
+    assert_eq!(resp.url(), "https://api.example.com/data");
+
+    let cache = ResponseCache::new("gloo-net-test-network-first", CachePolicy::NetworkFirst);
+    cache.clear().await.unwrap();
+    let client = Client::builder()
+        .base_url("https://api.example.com")
+        .interceptor(cache)
+        .transport(mock.clone())
+        .build();
+    for _ in 0..2 {
+        let resp = client.get("/data").send().await.unwrap();
+        assert_eq!(resp.text().await.unwrap(), "cached");
+    }
+    let requests = mock.take_requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[1].headers().get("If-None-Match"), None);
+    assert_eq!(
+        requests[2].headers().get("If-None-Match").as_deref(),
+        Some("\"v1\"")
+    );
+}
+
+#[wasm_bindgen_test]
+async fn deduplicate() {
+    let mock = MockTransport::new();
+    mock.on(Method::GET, "/shared")
+        .respond(Response::builder().body(Some("shared")).unwrap());
+    let client = Client::builder()
+        .base_url("https://api.example.com")
+        .interceptor(Deduplicate::new())
+        .transport(mock.clone())
+        .build();
+
+    let (first, second) =
+        futures::join!(client.get("/shared").send(), client.get("/shared").send(),);
+    assert_eq!(first.unwrap().text().await.unwrap(), "shared");
+    assert_eq!(second.unwrap().text().await.unwrap(), "shared");
+    assert_eq!(mock.request_count(), 1);
+
+    // Trace contexts differ between requests for the same resource.
+    let (first, second) = futures::join!(
+        client
+            .get("/shared")
+            .header(
+                "traceparent",
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+            )
+            .send(),
+        client
+            .get("/shared")
+            .header(
+                "traceparent",
+                "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01"
+            )
+            .send(),
+    );
+    assert!(first.is_ok() && second.is_ok());
+    assert_eq!(mock.request_count(), 2);
+}
+
+#[wasm_bindgen_test]
+async fn concurrency_limit() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[derive(Clone, Default)]
+    struct Slow {
+        active: Rc<Cell<usize>>,
+        max_active: Rc<Cell<usize>>,
+    }
+
+    impl Transport for Slow {
+        fn send<'a>(&'a self, _request: Request) -> SendFuture<'a> {
+            Box::pin(async move {
+                self.active.set(self.active.get() + 1);
+                self.max_active
+                    .set(self.max_active.get().max(self.active.get()));
+                gloo_timers::future::TimeoutFuture::new(10).await;
+                self.active.set(self.active.get() - 1);
+                Response::builder().body(None::<&str>)
+            })
+        }
+    }
+
+    let transport = Slow::default();
+    let client = Client::builder()
+        .interceptor(ConcurrencyLimit::per_origin(2))
+        .transport(transport.clone())
+        .build();
+    let requests = (0..5).map(|i| client.get(&format!("https://a.example.com/{}", i)).send());
+    let responses = futures::future::join_all(requests).await;
+    assert!(responses.iter().all(Result::is_ok));
+    assert_eq!(transport.max_active.get(), 2);
+}
+
+#[cfg(feature = "download")]
+#[wasm_bindgen_test]
+async fn resumable_download() {
+    use gloo_net::http::Download;
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+
+    const CONTENT: &str = "the quick brown fox jumps over the lazy dog";
+
+    /// Serves byte ranges of `CONTENT`, failing the second request.
+    #[derive(Clone, Default)]
+    struct Ranges {
+        requests: Rc<Cell<usize>>,
+    }
+
+    impl Transport for Ranges {
+        fn send<'a>(&'a self, request: Request) -> SendFuture<'a> {
+            Box::pin(async move {
+                self.requests.set(self.requests.get() + 1);
+                if self.requests.get() == 2 {
+                    return Err(Error::Network {
+                        method: request.method(),
+                        url: request.url(),
+                        source: "connection reset".into(),
+                    });
+                }
+                let range = request.headers().get("Range").unwrap();
+                let (start, end) = range["bytes=".len()..].split_once('-').unwrap();
+                let start: usize = start.parse().unwrap();
+                let end = end.parse::<usize>().unwrap().min(CONTENT.len() - 1);
+                Response::builder()
+                    .status(206)
+                    .header("Content-Type", "text/plain")
+                    .header("ETag", "\"v1\"")
+                    .header(
+                        "Content-Range",
+                        &format!("bytes {}-{}/{}", start, end, CONTENT.len()),
+                    )
+                    .body(Some(&CONTENT[start..=end]))
+            })
+        }
+    }
+
+    let transport = Ranges::default();
+    let progress = Rc::new(RefCell::new(Vec::new()));
+    let blob = Download::new("https://files.example.com/fox.txt")
+        .chunk_size(16)
+        .retry(RetryPolicy::new().backoff(Duration::from_millis(1), Duration::from_millis(1)))
+        .transport(transport.clone())
+        .on_progress({
+            let progress = progress.clone();
+            move |p| progress.borrow_mut().push(p.loaded)
+        })
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(blob.raw_mime_type(), "text/plain");
+    let text = Response::from(web_sys::Response::new_with_opt_blob(Some(blob.as_ref())).unwrap())
+        .text()
+        .await
+        .unwrap();
+    assert_eq!(text, CONTENT);
+    assert_eq!(*progress.borrow(), vec![16, 32, 43]);
+    assert_eq!(transport.requests.get(), 4);
+}
+
+#[cfg(feature = "compression")]
+#[wasm_bindgen_test]
+async fn compression() {
+    use gloo_net::http::ContentEncoding;
+
+    /// Answers with the body of the request, as it was sent.
+    struct Echo;
+
+    impl Transport for Echo {
+        fn send<'a>(&'a self, request: Request) -> SendFuture<'a> {
+            Box::pin(async move {
+                let encoding = request.headers().get("Content-Encoding").unwrap();
+                let mut body = request.binary().await?;
+                Response::builder()
+                    .header("Content-Encoding", &encoding)
+                    .body(Some(body.as_mut_slice()))
+            })
+        }
+    }
+
+    for encoding in [ContentEncoding::Gzip, ContentEncoding::Deflate] {
+        let resp = Request::post("/upload")
+            .compress(encoding)
+            .transport(Echo)
+            .body("hello hello hello hello")
+            .unwrap()
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(
+            resp.headers().get("Content-Encoding").as_deref(),
+            Some(encoding.as_str())
+        );
+        let resp = resp.decompress(encoding).await.unwrap();
+        assert!(!resp.headers().has("Content-Encoding"));
+        assert_eq!(resp.text().await.unwrap(), "hello hello hello hello");
+    }
+}
+
+#[wasm_bindgen_test]
+fn typed_headers() {
+    use gloo_net::http::{CacheControl, ContentLength, ETag, Headers};
+
+    let headers = Headers::new();
+    headers.typed_set(&ETag::strong("v1").unwrap()).unwrap();
+    headers
+        .typed_set(&CacheControl {
+            no_cache: true,
+            ..CacheControl::default()
+        })
+        .unwrap();
+    assert_eq!(headers.get("ETag").as_deref(), Some("\"v1\""));
+    assert_eq!(headers.get("Cache-Control").as_deref(), Some("no-cache"));
+    assert_eq!(
+        headers.typed_get::<ETag>().unwrap(),
+        Some(ETag::strong("v1").unwrap())
+    );
+
+    headers.set("Content-Length", "many");
+    assert!(matches!(
+        headers.typed_get::<ContentLength>(),
+        Err(Error::InvalidHeader { .. })
+    ));
+}
+
+#[cfg(feature = "tracing")]
+#[wasm_bindgen_test]
+async fn traceparent() {
+    /// Answers with the `traceparent` header of the request.
+    struct Traceparent;
+
+    impl Transport for Traceparent {
+        fn send<'a>(&'a self, request: Request) -> SendFuture<'a> {
+            Box::pin(async move {
+                let traceparent = request.headers().get("traceparent").unwrap_or_default();
+                Response::builder().body(Some(traceparent.as_str()))
+            })
+        }
+    }
+
+    let traceparent = Request::get("/traced")
+        .transport(Traceparent)
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    let parts: Vec<_> = traceparent.split('-').collect();
+    assert_eq!(parts[0], "00");
+    assert_eq!(parts[1].len(), 32);
+    assert_eq!(parts[2].len(), 16);
+    assert_eq!(parts[3], "01");
+
+    let existing = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+    let traceparent = Request::get("/traced")
+        .header("traceparent", existing)
+        .transport(Traceparent)
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert_eq!(traceparent, existing);
+
+    // Cross-origin requests would need a CORS preflight for the header.
+    let traceparent = Request::get("https://api.example.com/traced")
+        .transport(Traceparent)
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert_eq!(traceparent, "");
+}
+
+#[cfg(feature = "har")]
+#[wasm_bindgen_test]
+async fn har_recorder() {
+    use gloo_net::http::HarRecorder;
+
+    let mock = MockTransport::new();
+    mock.on(Method::POST, "/login")
+        .respond(Response::builder().body(Some("welcome")).unwrap());
+    let recorder = HarRecorder::new()
+        .record_bodies(true)
+        .redact_header("X-Api-Key")
+        .redact_query("api_key")
+        .redact_body(|body| body.replace("hunter2", "***"));
+    let client = Client::builder()
+        .interceptor(recorder.clone())
+        .transport(mock)
+        .build();
+
+    client
+        .post("https://a.example.com/login?api_key=secret&lang=en")
+        .header("Authorization", "Bearer token")
+        .header("X-Api-Key", "secret")
+        .body("password=hunter2")
+        .unwrap()
+        .send()
+        .await
+        .unwrap();
+    assert!(client
+        .get("https://a.example.com/missing?api_key=secret")
+        .send()
+        .await
+        .is_err());
+    assert_eq!(recorder.len(), 2);
+
+    let har = recorder.to_har();
+    assert_eq!(har["log"]["version"], "1.2");
+    let entry = &har["log"]["entries"][0];
+    let request = &entry["request"];
+    assert_eq!(request["method"], "POST");
+    assert_eq!(
+        request["url"],
+        "https://a.example.com/login?api_key=%5Bredacted%5D&lang=en"
+    );
+    assert_eq!(request["queryString"][0]["value"], "[redacted]");
+    let header = |name: &str| {
+        request["headers"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|header| header["name"] == name)
+            .map(|header| header["value"].clone())
+    };
+    assert_eq!(header("authorization").unwrap(), "[redacted]");
+    assert_eq!(header("x-api-key").unwrap(), "[redacted]");
+    assert_eq!(request["postData"]["text"], "password=***");
+    let response = &entry["response"];
+    assert_eq!(response["status"], 200);
+    assert_eq!(response["content"]["text"], "welcome");
+    let failed = &har["log"]["entries"][1]["response"];
+    assert_eq!(failed["status"], 0);
+    let error = failed["_error"].as_str().unwrap();
+    assert!(error.contains("https://a.example.com/missing?api_key=%5Bredacted%5D"));
+    assert!(!error.contains("secret"));
+    assert!(recorder.to_blob().size() > 0);
+}